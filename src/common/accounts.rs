@@ -2,13 +2,17 @@ use anyhow::{Result, anyhow};
 use ethers::signers::{LocalWallet, MnemonicBuilder, Signer};
 use ethers::types::Address;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedMutexGuard;
 
 /// Anvil's default mnemonic phrase
-const ANVIL_MNEMONIC: &str = "test test test test test test test test test test test junk";
+pub(crate) const ANVIL_MNEMONIC: &str =
+    "test test test test test test test test test test test junk";
 
 pub struct Account {
     pub(crate) wallet: LocalWallet,
     pub(crate) derivation_path: String,
+    pub(crate) index: u32,
 }
 
 pub struct Accounts {
@@ -39,6 +43,7 @@ impl Accounts {
             let account = Account {
                 wallet,
                 derivation_path: derivation_path.clone(),
+                index: i,
             };
 
             wallets.insert(address, account);
@@ -55,21 +60,25 @@ impl Accounts {
         self.wallets.get(address).map(|account| &account.wallet)
     }
 
-    /// Get Default wallet
+    /// Get Default wallet, the first account in derivation order
+    #[allow(dead_code)]
     pub fn default_wallet(&self) -> Option<&LocalWallet> {
-        let addresses = self.addresses();
-        if !addresses.is_empty() {
-            let first_address = addresses[0];
-            return self.get_wallet(&first_address);
-        }
-        None
+        self.ordered().first().map(|(_, account)| &account.wallet)
     }
 
     /// Get all addresses
+    #[allow(dead_code)]
     pub fn addresses(&self) -> Vec<Address> {
         self.wallets.keys().cloned().collect()
     }
 
+    /// All accounts in derivation order
+    pub fn ordered(&self) -> Vec<(&Address, &Account)> {
+        let mut accounts: Vec<_> = self.wallets.iter().collect();
+        accounts.sort_by_key(|(_, account)| account.index);
+        accounts
+    }
+
     /// Print all account information
     /// (TESTING ONLY)
     #[allow(dead_code)]
@@ -78,10 +87,7 @@ impl Accounts {
         println!("Mnemonic: {}", self.mnemonic);
         println!();
 
-        let mut addresses: Vec<_> = self.wallets.iter().collect();
-        addresses.sort_by_key(|(_, account)| &account.derivation_path);
-
-        for (i, (address, account)) in addresses.iter().enumerate() {
+        for (i, (address, account)) in self.ordered().iter().enumerate() {
             println!("Account #{i}: {address}");
             println!(
                 "  Private Key: 0x{}",
@@ -99,9 +105,33 @@ impl Default for Accounts {
     }
 }
 
+/// Per-account locks used to serialize transactions sent from the same account.
+///
+/// Holding the guard while a transaction is built, sent and confirmed keeps nonces sequential
+/// for that account, while other accounts and read-only queries proceed concurrently.
+#[derive(Default)]
+pub struct SignerLocks {
+    locks: Mutex<HashMap<Address, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl SignerLocks {
+    /// Wait for exclusive use of an account, the lock is released when the guard is dropped
+    pub async fn lock(&self, address: Address) -> OwnedMutexGuard<()> {
+        let lock = self
+            .locks
+            .lock()
+            .expect("signer locks poisoned")
+            .entry(address)
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_anvil_accounts_creation() {
@@ -131,4 +161,21 @@ mod tests {
         assert!(wallet.is_some());
         assert_eq!(wallet.unwrap().address(), first_address);
     }
+
+    #[tokio::test]
+    async fn test_signer_locks_are_per_account() {
+        let locks = SignerLocks::default();
+        let first = Address::from_low_u64_be(1);
+        let second = Address::from_low_u64_be(2);
+
+        let _guard = locks.lock(first).await;
+
+        // A different account is not blocked by the held lock
+        let other = tokio::time::timeout(Duration::from_millis(100), locks.lock(second)).await;
+        assert!(other.is_ok());
+
+        // The same account has to wait until the guard is dropped
+        let same = tokio::time::timeout(Duration::from_millis(100), locks.lock(first)).await;
+        assert!(same.is_err());
+    }
 }
@@ -0,0 +1,453 @@
+//! # Chains Module
+//!
+//! Registry of the EVM chains the server operates on. Every tool that talks to a chain accepts an
+//! optional chain selector (name or chain id) and falls back to the default chain when omitted.
+//!
+//! Each chain carries:
+//! - **Chain id** and **name** used to select it
+//! - **RPC URL** used to build its provider
+//! - **Native symbol** and **wrapped native token** address (WETH on Ethereum and its L2s)
+//! - **Uniswap V2 router** address, used by the uniswap tools
+//! - **Uniswap V3 SwapRouter** and **QuoterV2** addresses, used by the uniswap v3 tools
+//! - **Explorer URL**, used to link transactions in tool results
+//! - **Token list**, letting token inputs take symbols, see [`crate::common::tokens`]
+//!
+//! Well known chains (`mainnet`, `base`, `arbitrum`, `anvil`) are built in as presets, so a config
+//! entry for them only needs an RPC URL. Any preset field can be overridden, and other chains can
+//! be added by giving all of their fields. The `anvil` preset assumes a fork of mainnet, so it
+//! uses the mainnet WETH and router addresses. Base has no preset V3 router, as the original
+//! SwapRouter isn't deployed there.
+use crate::common::context::ConfigProblem;
+use crate::common::error::ToolError;
+use crate::common::tokens::TokenList;
+use anyhow::{Result, anyhow};
+use ethers::prelude::{Address, H256, Http, Provider};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Name of the chain used when no default chain is configured
+pub(crate) const DEFAULT_CHAIN: &str = "mainnet";
+
+/// Time allowed for a single RPC request before it's reported as timed out
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Static description of a well known chain.
+struct ChainPreset {
+    name: &'static str,
+    chain_id: u64,
+    native_symbol: &'static str,
+    wrapped_native: &'static str,
+    uniswap_router: &'static str,
+    uniswap_v3_router: Option<&'static str>,
+    uniswap_v3_quoter: Option<&'static str>,
+    explorer_url: Option<&'static str>,
+}
+
+const PRESETS: &[ChainPreset] = &[
+    ChainPreset {
+        name: "mainnet",
+        chain_id: 1,
+        native_symbol: "ETH",
+        wrapped_native: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        uniswap_router: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+        uniswap_v3_router: Some("0xE592427A0AEce92De3Edee1F18E0157C05861564"),
+        uniswap_v3_quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        explorer_url: Some("https://etherscan.io"),
+    },
+    ChainPreset {
+        name: "base",
+        chain_id: 8453,
+        native_symbol: "ETH",
+        wrapped_native: "0x4200000000000000000000000000000000000006",
+        uniswap_router: "0x4752ba5DBc23f44D87826276BF6Fd6b1C372aD24",
+        uniswap_v3_router: None,
+        uniswap_v3_quoter: Some("0x3d4e44Eb1374240CE5F1B871ab261CD16335B76a"),
+        explorer_url: Some("https://basescan.org"),
+    },
+    ChainPreset {
+        name: "arbitrum",
+        chain_id: 42161,
+        native_symbol: "ETH",
+        wrapped_native: "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+        uniswap_router: "0x4752ba5DBc23f44D87826276BF6Fd6b1C372aD24",
+        uniswap_v3_router: Some("0xE592427A0AEce92De3Edee1F18E0157C05861564"),
+        uniswap_v3_quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        explorer_url: Some("https://arbiscan.io"),
+    },
+    ChainPreset {
+        name: "anvil",
+        chain_id: 31337,
+        native_symbol: "ETH",
+        wrapped_native: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        uniswap_router: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+        uniswap_v3_router: Some("0xE592427A0AEce92De3Edee1F18E0157C05861564"),
+        uniswap_v3_quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e"),
+        explorer_url: None,
+    },
+];
+
+/// Fully resolved configuration of a single chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub(crate) name: String,
+    pub(crate) chain_id: u64,
+    pub(crate) rpc_url: String,
+    pub(crate) native_symbol: String,
+    pub(crate) wrapped_native: Option<Address>,
+    pub(crate) uniswap_router: Option<Address>,
+    pub(crate) uniswap_v3_router: Option<Address>,
+    pub(crate) uniswap_v3_quoter: Option<Address>,
+    pub(crate) explorer_url: Option<String>,
+    pub(crate) tokens: TokenList,
+}
+
+/// A `[[chains]]` entry as read from the config file, missing fields are filled from the presets.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ChainLayer {
+    pub(crate) name: String,
+    pub(crate) chain_id: Option<u64>,
+    pub(crate) rpc_url: Option<String>,
+    pub(crate) native_symbol: Option<String>,
+    pub(crate) wrapped_native: Option<String>,
+    pub(crate) uniswap_router: Option<String>,
+    pub(crate) uniswap_v3_router: Option<String>,
+    pub(crate) uniswap_v3_quoter: Option<String>,
+    pub(crate) explorer_url: Option<String>,
+    /// Path of a token list file, relative to the working directory
+    pub(crate) token_list: Option<String>,
+}
+
+impl ChainLayer {
+    /// Resolves the entry against the presets, recording every missing or invalid field.
+    ///
+    /// The default chain's RPC URL comes from the `eth` section, so a missing one is reported
+    /// against that field instead.
+    pub(crate) fn resolve(
+        self,
+        is_default: bool,
+        problems: &mut Vec<ConfigProblem>,
+    ) -> Option<ChainConfig> {
+        let field = |name: &str| format!("chains.{}.{name}", self.name);
+        let preset = PRESETS
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(&self.name));
+        let start = problems.len();
+
+        let chain_id = self
+            .chain_id
+            .or(preset.map(|p| p.chain_id))
+            .unwrap_or_else(|| {
+                problems.push(ConfigProblem::Missing {
+                    field: field("chain_id"),
+                    env: None,
+                });
+                0
+            });
+
+        let rpc_url = self.rpc_url.filter(|url| !url.trim().is_empty());
+        match &rpc_url {
+            None if is_default => problems.push(ConfigProblem::Missing {
+                field: String::from("eth.rpc_url"),
+                env: Some(crate::common::ENV_ETH_RPC),
+            }),
+            None => problems.push(ConfigProblem::Missing {
+                field: field("rpc_url"),
+                env: None,
+            }),
+            Some(url) => {
+                if let Err(e) = reqwest::Url::parse(url) {
+                    problems.push(ConfigProblem::Invalid {
+                        field: field("rpc_url"),
+                        reason: format!("not a valid url: {e}"),
+                    });
+                }
+            }
+        }
+
+        let mut address = |value: Option<String>, preset: Option<&str>, name: &str| {
+            let value = value.or(preset.map(String::from))?;
+            value
+                .parse::<Address>()
+                .map_err(|e| {
+                    problems.push(ConfigProblem::Invalid {
+                        field: field(name),
+                        reason: format!("not a valid address: {e}"),
+                    })
+                })
+                .ok()
+        };
+        let wrapped_native = address(
+            self.wrapped_native,
+            preset.map(|p| p.wrapped_native),
+            "wrapped_native",
+        );
+        let uniswap_router = address(
+            self.uniswap_router,
+            preset.map(|p| p.uniswap_router),
+            "uniswap_router",
+        );
+        let uniswap_v3_router = address(
+            self.uniswap_v3_router,
+            preset.and_then(|p| p.uniswap_v3_router),
+            "uniswap_v3_router",
+        );
+        let uniswap_v3_quoter = address(
+            self.uniswap_v3_quoter,
+            preset.and_then(|p| p.uniswap_v3_quoter),
+            "uniswap_v3_quoter",
+        );
+
+        let tokens = match &self.token_list {
+            Some(path) => TokenList::load(Path::new(path), chain_id).unwrap_or_else(|reason| {
+                problems.push(ConfigProblem::Invalid {
+                    field: field("token_list"),
+                    reason: format!("{path}: {reason}"),
+                });
+                TokenList::default()
+            }),
+            None => TokenList::default(),
+        };
+
+        if problems.len() > start {
+            return None;
+        }
+
+        Some(ChainConfig {
+            name: preset.map_or(self.name, |p| p.name.to_string()),
+            chain_id,
+            rpc_url: rpc_url.unwrap_or_default(),
+            native_symbol: self
+                .native_symbol
+                .or(preset.map(|p| p.native_symbol.to_string()))
+                .unwrap_or_else(|| String::from("ETH")),
+            wrapped_native,
+            uniswap_router,
+            uniswap_v3_router,
+            uniswap_v3_quoter,
+            explorer_url: self
+                .explorer_url
+                .or(preset.and_then(|p| p.explorer_url.map(String::from)))
+                .map(|url| url.trim_end_matches('/').to_string()),
+            tokens,
+        })
+    }
+}
+
+/// A configured chain together with its provider.
+pub struct Chain {
+    pub(crate) config: ChainConfig,
+    pub(crate) provider: Arc<Provider<Http>>,
+}
+
+impl Chain {
+    /// Wrapped native token (e.g. WETH) used for swap paths
+    pub fn wrapped_native(&self) -> Result<Address> {
+        self.config.wrapped_native.ok_or_else(|| {
+            ToolError::invalid_params(
+                "chain",
+                format!(
+                    "no wrapped native token configured for chain {}",
+                    self.config.name
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// Whether an address is one of the Uniswap routers configured for this chain, the only
+    /// routers swaps approve automatically
+    pub fn is_uniswap_router(&self, address: Address) -> bool {
+        [self.config.uniswap_router, self.config.uniswap_v3_router].contains(&Some(address))
+    }
+
+    /// Uniswap V2 router deployed on this chain
+    pub fn uniswap_router(&self) -> Result<Address> {
+        self.config.uniswap_router.ok_or_else(|| {
+            ToolError::invalid_params(
+                "chain",
+                format!(
+                    "no uniswap router configured for chain {}",
+                    self.config.name
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// Uniswap V3 SwapRouter deployed on this chain
+    pub fn uniswap_v3_router(&self) -> Result<Address> {
+        self.config.uniswap_v3_router.ok_or_else(|| {
+            ToolError::invalid_params(
+                "chain",
+                format!(
+                    "no uniswap v3 router configured for chain {}",
+                    self.config.name
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// Uniswap V3 QuoterV2 deployed on this chain
+    pub fn uniswap_v3_quoter(&self) -> Result<Address> {
+        self.config.uniswap_v3_quoter.ok_or_else(|| {
+            ToolError::invalid_params(
+                "chain",
+                format!(
+                    "no uniswap v3 quoter configured for chain {}",
+                    self.config.name
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// Link to a transaction on the chain's block explorer, if one is configured
+    pub fn tx_url(&self, hash: H256) -> Option<String> {
+        self.config
+            .explorer_url
+            .as_ref()
+            .map(|url| format!("{url}/tx/{hash:?}"))
+    }
+}
+
+/// All chains the server can operate on, with one of them used as the default.
+pub struct ChainRegistry {
+    chains: Vec<Chain>,
+    default: usize,
+}
+
+impl ChainRegistry {
+    /// Builds a provider for every configured chain
+    pub fn new(configs: &[ChainConfig], default_chain: &str) -> Result<Self> {
+        let mut chains = Vec::with_capacity(configs.len());
+        for config in configs {
+            tracing::info!("Creating provider for chain {}", config.name);
+            let url = config
+                .rpc_url
+                .parse::<ethers_reqwest::Url>()
+                .map_err(|e| anyhow!("invalid rpc url for chain {}: {e}", config.name))?;
+            // ethers has its own reqwest version, the client is built with that one
+            let client = ethers_reqwest::Client::builder()
+                .timeout(RPC_TIMEOUT)
+                .build()
+                .map_err(|e| anyhow!("failed to build provider for chain {}: {e}", config.name))?;
+            let provider = Provider::new(Http::new_with_client(url, client));
+            chains.push(Chain {
+                config: config.clone(),
+                provider: Arc::new(provider),
+            });
+        }
+
+        let default = chains
+            .iter()
+            .position(|chain| chain.config.name.eq_ignore_ascii_case(default_chain))
+            .ok_or_else(|| anyhow!("default chain {default_chain} is not configured"))?;
+        Ok(Self { chains, default })
+    }
+
+    /// Selects a chain by name or chain id, or the default chain when no selector is given
+    pub fn get(&self, selector: Option<&str>) -> Result<&Chain> {
+        let Some(selector) = selector.map(str::trim).filter(|s| !s.is_empty()) else {
+            return Ok(&self.chains[self.default]);
+        };
+        let chain_id = selector.parse::<u64>().ok();
+        self.chains
+            .iter()
+            .find(|chain| {
+                chain.config.name.eq_ignore_ascii_case(selector)
+                    || Some(chain.config.chain_id) == chain_id
+            })
+            .ok_or_else(|| {
+                let known = self
+                    .chains
+                    .iter()
+                    .map(|chain| chain.config.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                ToolError::NotFound {
+                    what: String::from("chain"),
+                    name: selector.to_string(),
+                    detail: Some(format!("configured chains: {known}")),
+                }
+                .into()
+            })
+    }
+
+    /// The chain used when a tool call doesn't select one
+    pub fn default_chain(&self) -> &Chain {
+        &self.chains[self.default]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Chain> {
+        self.chains.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_fills_missing_fields() {
+        let layer = ChainLayer {
+            name: String::from("Base"),
+            rpc_url: Some(String::from("https://mainnet.base.org")),
+            ..Default::default()
+        };
+        let mut problems = Vec::new();
+        let chain = layer.resolve(false, &mut problems).unwrap();
+
+        assert!(problems.is_empty());
+        assert_eq!(chain.name, "base");
+        assert_eq!(chain.chain_id, 8453);
+        assert!(chain.wrapped_native.is_some());
+        assert!(chain.uniswap_v3_router.is_none());
+        assert!(chain.uniswap_v3_quoter.is_some());
+        assert_eq!(chain.explorer_url.as_deref(), Some("https://basescan.org"));
+    }
+
+    #[test]
+    fn test_unknown_chain_requires_chain_id() {
+        let layer = ChainLayer {
+            name: String::from("devnet"),
+            ..Default::default()
+        };
+        let mut problems = Vec::new();
+
+        assert!(layer.resolve(false, &mut problems).is_none());
+        assert_eq!(problems.len(), 2);
+        assert!(problems.contains(&ConfigProblem::Missing {
+            field: String::from("chains.devnet.chain_id"),
+            env: None
+        }));
+        assert!(problems.contains(&ConfigProblem::Missing {
+            field: String::from("chains.devnet.rpc_url"),
+            env: None
+        }));
+    }
+
+    #[test]
+    fn test_registry_selects_by_name_or_id() {
+        let configs: Vec<ChainConfig> = ["mainnet", "anvil"]
+            .into_iter()
+            .map(|name| {
+                let layer = ChainLayer {
+                    name: name.to_string(),
+                    rpc_url: Some(String::from("http://localhost:8545")),
+                    ..Default::default()
+                };
+                layer.resolve(false, &mut Vec::new()).unwrap()
+            })
+            .collect();
+        let registry = ChainRegistry::new(&configs, "anvil").unwrap();
+
+        assert_eq!(registry.get(None).unwrap().config.name, "anvil");
+        assert_eq!(registry.get(Some("MAINNET")).unwrap().config.chain_id, 1);
+        assert_eq!(registry.get(Some("31337")).unwrap().config.name, "anvil");
+        assert!(registry.get(Some("base")).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Command line arguments for the MCP server.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "An MCP server providing EVM, DeFi and web search tools"
+)]
+pub struct Cli {
+    /// Transport used to serve the MCP protocol
+    #[arg(long, value_enum, default_value_t = Transport::Http)]
+    pub transport: Transport,
+
+    /// Path to a TOML config file, environment variables override values set in the file
+    #[arg(long, env = "MCP_CONFIG")]
+    pub config: Option<PathBuf>,
+}
+
+/// Transports the server can be started with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Transport {
+    /// HTTP server exposing the streamable HTTP endpoint (`/mcp`) and the legacy SSE endpoints
+    /// (`/sse` and `/message`)
+    #[value(alias = "sse")]
+    Http,
+    /// Serve a single client over stdin/stdout, for clients that spawn the server as a subprocess
+    Stdio,
+}
@@ -10,61 +10,133 @@
 //! A generic wrapper that holds a multi-tool instance implementing all required trait interfaces.
 //! The type parameter `T` must implement:
 //! - `EvmTools`: For Ethereum blockchain interactions
+//! - `Erc20Tools`: For ERC20 transfers and approvals
 //! - `BraveTools`: For web search capabilities
 //! - `UniSwapTools`: For Uniswap protocol operations
+//! - `UniSwapV3Tools`: For Uniswap V3 swaps and quotes
 //! - `ZeroXTools`: For 0x protocol integration
-//! - `Send`: For thread safety across async boundaries
+//! - `TokenListTools`: For searching the chains' token lists
+//! - `ResourceTools`: For the read-only context served as MCP resources
+//! - `Send` + `Sync`: For sharing across sessions and concurrent tool calls without a global lock
 //!
 //! This design allows for dependency injection and makes the system highly testable by
 //! accepting any implementation that satisfies the required trait bounds.
 //!
 //! ### `Config`
-//! Configuration structure that manages all external service credentials and endpoints:
-//! - **ETH RPC URL**: Ethereum node connection endpoint
-//! - **Brave API Key**: Authentication for Brave Search API
-//! - **0x API Key**: Authentication for 0x Protocol API
+//! Configuration structure that manages the server settings, external service credentials and
+//! endpoints:
+//! - **Server**: Bind address and port for the HTTP transports
+//! - **Chains**: Registry of EVM chains with their RPC URLs, the default chain is set by `eth`
+//! - **Accounts**: Mnemonic and number of managed accounts derived from it
+//! - **Brave**: Authentication and result count for Brave Search API (optional)
+//! - **0x API Key**: Authentication for 0x Protocol API (optional)
+//! - **Uniswap**: Default slippage tolerance and deadline of swaps, and the maximum slippage a swap
+//!   may request
+//!
+//! Only the RPC URL of the default chain is required. The Brave and 0x tool groups are enabled only
+//! when their API keys are present, the Uniswap tool group only when a chain has a Uniswap
+//! router, and the token list tool group only when a chain has a token list. Tools of disabled
+//! groups are not exposed by the server.
 //!
 //! ## Configuration Management
 //!
-//! The `Config` struct automatically loads all required configuration from environment variables:
-//! - `ETH_RPC`: Ethereum RPC endpoint URL
-//! - `BRAVE_API_KEY`: Brave Search API authentication key
-//! - `ZERO_X_API_KEY`: 0x Protocol API authentication key
+//! Configuration is loaded in layers, each layer overriding the previous one:
+//! 1. Built-in defaults for optional settings
+//! 2. An optional TOML config file (`--config <PATH>` or `MCP_CONFIG`)
+//! 3. Environment variables
+//!
+//! | Field                      | Environment variable       | Default                  |
+//! |----------------------------|----------------------------|--------------------------|
+//! | `server.address`           | `MCP_SERVER_ADDRESS`       | `127.0.0.1`              |
+//! | `server.port`              | `MCP_SERVER_PORT`          | `8000`                   |
+//! | `eth.rpc_url`              | `ETH_RPC`                  | required                 |
+//! | `eth.chain`                | `ETH_CHAIN`                | `mainnet`                |
+//! | `accounts.mnemonic`        | `ACCOUNTS_MNEMONIC`        | Anvil's default mnemonic |
+//! | `accounts.count`           | `ACCOUNTS_COUNT`           | `10`                     |
+//! | `brave.api_key`            | `BRAVE_API_KEY`            | unset, disables brave    |
+//! | `brave.result_count`       | `BRAVE_RESULT_COUNT`       | `3`                      |
+//! | `zero_x.api_key`           | `ZERO_X_API_KEY`           | unset, disables 0x       |
+//! | `uniswap.slippage_bps`     | `UNISWAP_SLIPPAGE_BPS`     | `50` (0.5%)              |
+//! | `uniswap.max_slippage_bps` | `UNISWAP_MAX_SLIPPAGE_BPS` | `1000` (10%)             |
+//! | `uniswap.deadline_secs`    | `UNISWAP_DEADLINE_SECS`    | `300`                    |
 //!
-//! All environment variables are required and the application will panic on startup if any
-//! are missing, ensuring fail-fast behavior for configuration issues.
+//! Additional chains are configured with `[[chains]]` entries, see [`crate::common::chains`].
+//! An entry named like the default chain is overridden by the `eth` section.
+//!
+//! Loading never panics, every missing or invalid field is collected into a single
+//! [`ConfigError`] so all problems can be fixed at once.
 //!
 //! ## Usage
 //!
 //! ```rust
-//! // Load configuration from environment
-//! let config = Config::new();
+//! // Load configuration from an optional file and the environment
+//! let config = Config::load(Some(Path::new("config.toml")))?;
 //!
 //! // Create multi-tool with config
-//! let multitool = MultiTool::new(&config);
+//! let multitool = MultiTool::new(&config)?;
 //!
 //! // Wrap in context for dependency injection
 //! let context = Context::new(multitool);
 //! ```
-//!
-//! ## Future Extensibility
-//!
-//! The `Config` struct is designed with serialization support (`Serialize`/`Deserialize`)
-//! to enable future persistence to disk-based configuration files as the configuration
-//! grows in complexity.
-use crate::common::{ENV_BRAVE_API_KEY, ENV_ETH_RPC, ENV_ZERO_X_API_KEY, get_env_var};
-use crate::tools::traits::{BraveTools, EvmTools, UniSwapTools, ZeroXTools};
+use crate::common::accounts::ANVIL_MNEMONIC;
+use crate::common::chains::{ChainConfig, ChainLayer, DEFAULT_CHAIN};
+use crate::common::{
+    ENV_ACCOUNTS_COUNT, ENV_ACCOUNTS_MNEMONIC, ENV_BRAVE_API_KEY, ENV_BRAVE_RESULT_COUNT,
+    ENV_ETH_CHAIN, ENV_ETH_RPC, ENV_SERVER_ADDRESS, ENV_SERVER_PORT, ENV_UNISWAP_DEADLINE_SECS,
+    ENV_UNISWAP_MAX_SLIPPAGE_BPS, ENV_UNISWAP_SLIPPAGE_BPS, ENV_ZERO_X_API_KEY, get_env_var,
+};
+use crate::tools::traits::{
+    BraveTools, Erc20Tools, EvmTools, ResourceTools, TokenListTools, UniSwapTools, UniSwapV3Tools,
+    ZeroXTools,
+};
+use ethers::signers::coins_bip39::{English, Mnemonic};
 use serde::{Deserialize, Serialize};
-use std::marker::Send;
+use std::fmt::{self, Display};
+use std::marker::{Send, Sync};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1";
+const DEFAULT_SERVER_PORT: u16 = 8000;
+const DEFAULT_ACCOUNT_COUNT: u32 = 10;
+const DEFAULT_BRAVE_RESULT_COUNT: u32 = 3;
+/// Brave Search API accepts at most 20 results per request
+pub(crate) const MAX_BRAVE_RESULT_COUNT: u32 = 20;
+const DEFAULT_UNISWAP_SLIPPAGE_BPS: u32 = 50;
+const DEFAULT_UNISWAP_MAX_SLIPPAGE_BPS: u32 = 1_000;
+const DEFAULT_UNISWAP_DEADLINE_SECS: u64 = 300;
+/// Basis points in 100%
+pub const MAX_BPS: u32 = 10_000;
 
 pub struct Context<T>
 where
-    T: EvmTools + BraveTools + UniSwapTools + ZeroXTools + Send,
+    T: EvmTools
+        + Erc20Tools
+        + BraveTools
+        + UniSwapTools
+        + UniSwapV3Tools
+        + ZeroXTools
+        + TokenListTools
+        + ResourceTools
+        + Send
+        + Sync,
 {
     pub m_tool: T,
 }
 
-impl<T: EvmTools + BraveTools + UniSwapTools + ZeroXTools + Send> Context<T> {
+impl<
+    T: EvmTools
+        + Erc20Tools
+        + BraveTools
+        + UniSwapTools
+        + UniSwapV3Tools
+        + ZeroXTools
+        + TokenListTools
+        + ResourceTools
+        + Send
+        + Sync,
+> Context<T>
+{
     pub fn new(m_tool: T) -> Self {
         Self { m_tool }
     }
@@ -72,25 +144,555 @@ impl<T: EvmTools + BraveTools + UniSwapTools + ZeroXTools + Send> Context<T> {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
-    pub(crate) eth_rpc: String,
-    pub(crate) brave_api_key: String,
-    pub(crate) zero_x_api_key: String,
+    pub(crate) server: ServerConfig,
+    pub(crate) default_chain: String,
+    pub(crate) chains: Vec<ChainConfig>,
+    pub(crate) accounts: AccountsConfig,
+    pub(crate) brave: Option<BraveConfig>,
+    pub(crate) zero_x: Option<ZeroXConfig>,
+    pub(crate) uniswap: UniswapConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub(crate) address: String,
+    pub(crate) port: u16,
+}
+
+impl ServerConfig {
+    /// Address the HTTP transports bind to, formatted as `address:port`
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.address, self.port)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountsConfig {
+    pub(crate) mnemonic: String,
+    pub(crate) count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BraveConfig {
+    pub(crate) api_key: String,
+    pub(crate) result_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ZeroXConfig {
+    pub(crate) api_key: String,
+}
+
+/// Swap settings, tools may override the slippage and deadline per swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniswapConfig {
+    /// Slippage tolerance used when a swap doesn't set one, in basis points
+    pub(crate) slippage_bps: u32,
+    /// Highest slippage tolerance a swap may request, in basis points
+    pub(crate) max_slippage_bps: u32,
+    /// Seconds a swap stays valid when it doesn't set a deadline
+    pub(crate) deadline_secs: u64,
 }
 
 impl Config {
-    pub fn new() -> Self {
-        Self::default()
+    /// Loads the configuration from an optional TOML file, overridden by environment variables.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut problems = Vec::new();
+
+        let mut layer = match path {
+            Some(path) => ConfigLayer::from_file(path).unwrap_or_else(|problem| {
+                problems.push(problem);
+                ConfigLayer::default()
+            }),
+            None => ConfigLayer::default(),
+        };
+        layer.apply_env(|name| get_env_var(name).ok(), &mut problems);
+        layer.validate(problems)
+    }
+}
+
+/// A single missing or invalid configuration field.
+#[derive(Debug, PartialEq)]
+pub enum ConfigProblem {
+    /// The config file could not be read or parsed
+    File { path: PathBuf, reason: String },
+    /// A required field was set neither in the config file nor in the environment
+    Missing {
+        field: String,
+        env: Option<&'static str>,
+    },
+    /// A field was set to a value that can't be used
+    Invalid { field: String, reason: String },
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigProblem::File { path, reason } => {
+                write!(f, "config file {}: {reason}", path.display())
+            }
+            ConfigProblem::Missing {
+                field,
+                env: Some(env),
+            } => write!(
+                f,
+                "{field}: missing, set it in the config file or with the {env} environment variable"
+            ),
+            ConfigProblem::Missing { field, env: None } => {
+                write!(f, "{field}: missing, set it in the config file")
+            }
+            ConfigProblem::Invalid { field, reason } => write!(f, "{field}: {reason}"),
+        }
     }
 }
 
-/// Get all configuration fields from environment variables for now. If this gets bigger
-/// it can be persisted to disk as file using Serialize.
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            eth_rpc: get_env_var(ENV_ETH_RPC).expect("ETH_RPC not set"),
-            brave_api_key: get_env_var(ENV_BRAVE_API_KEY).expect("BRAVE_API_KEY not set"),
-            zero_x_api_key: get_env_var(ENV_ZERO_X_API_KEY).expect("ZERO_X_API_KEY not set"),
+/// Error returned when the configuration can't be loaded, lists every problem that was found.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub(crate) problems: Vec<ConfigProblem>,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid configuration ({} problems):",
+            self.problems.len()
+        )?;
+        for problem in &self.problems {
+            write!(f, "\n  - {problem}")?;
         }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Configuration as read from the config file, before environment overrides and validation.
+///
+/// Every field is optional so that environment variables can fill in the gaps and validation can
+/// report all missing fields at once.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigLayer {
+    server: ServerLayer,
+    eth: EthLayer,
+    accounts: AccountsLayer,
+    brave: BraveLayer,
+    zero_x: ZeroXLayer,
+    uniswap: UniswapLayer,
+    chains: Vec<ChainLayer>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerLayer {
+    address: Option<String>,
+    port: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct EthLayer {
+    rpc_url: Option<String>,
+    chain: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AccountsLayer {
+    mnemonic: Option<String>,
+    count: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BraveLayer {
+    api_key: Option<String>,
+    result_count: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ZeroXLayer {
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UniswapLayer {
+    slippage_bps: Option<u32>,
+    max_slippage_bps: Option<u32>,
+    deadline_secs: Option<u64>,
+}
+
+impl ConfigLayer {
+    fn from_file(path: &Path) -> Result<Self, ConfigProblem> {
+        let file_problem = |reason: String| ConfigProblem::File {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| file_problem(e.to_string()))?;
+        Self::from_toml(&contents).map_err(file_problem)
+    }
+
+    fn from_toml(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.message().to_string())
+    }
+
+    /// Overrides file values with the environment variables returned by `lookup`
+    fn apply_env(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+        problems: &mut Vec<ConfigProblem>,
+    ) {
+        let set = |target: &mut Option<String>, env: &str| {
+            if let Some(value) = lookup(env) {
+                *target = Some(value);
+            }
+        };
+        set(&mut self.server.address, ENV_SERVER_ADDRESS);
+        set(&mut self.eth.rpc_url, ENV_ETH_RPC);
+        set(&mut self.eth.chain, ENV_ETH_CHAIN);
+        set(&mut self.accounts.mnemonic, ENV_ACCOUNTS_MNEMONIC);
+        set(&mut self.brave.api_key, ENV_BRAVE_API_KEY);
+        set(&mut self.zero_x.api_key, ENV_ZERO_X_API_KEY);
+
+        parse_env(
+            &lookup,
+            &mut self.server.port,
+            "server.port",
+            ENV_SERVER_PORT,
+            problems,
+        );
+        parse_env(
+            &lookup,
+            &mut self.accounts.count,
+            "accounts.count",
+            ENV_ACCOUNTS_COUNT,
+            problems,
+        );
+        parse_env(
+            &lookup,
+            &mut self.brave.result_count,
+            "brave.result_count",
+            ENV_BRAVE_RESULT_COUNT,
+            problems,
+        );
+        parse_env(
+            &lookup,
+            &mut self.uniswap.slippage_bps,
+            "uniswap.slippage_bps",
+            ENV_UNISWAP_SLIPPAGE_BPS,
+            problems,
+        );
+        parse_env(
+            &lookup,
+            &mut self.uniswap.max_slippage_bps,
+            "uniswap.max_slippage_bps",
+            ENV_UNISWAP_MAX_SLIPPAGE_BPS,
+            problems,
+        );
+        parse_env(
+            &lookup,
+            &mut self.uniswap.deadline_secs,
+            "uniswap.deadline_secs",
+            ENV_UNISWAP_DEADLINE_SECS,
+            problems,
+        );
     }
-}
\ No newline at end of file
+
+    /// Fills in defaults and checks every field, collecting all problems into one error
+    fn validate(self, mut problems: Vec<ConfigProblem>) -> Result<Config, ConfigError> {
+        let (default_chain, chains) = resolve_chains(self.eth, self.chains, &mut problems);
+
+        let mnemonic = self
+            .accounts
+            .mnemonic
+            .unwrap_or_else(|| ANVIL_MNEMONIC.to_string());
+        if let Err(e) = Mnemonic::<English>::new_from_phrase(&mnemonic) {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("accounts.mnemonic"),
+                reason: format!("not a valid mnemonic: {e}"),
+            });
+        }
+
+        let count = self.accounts.count.unwrap_or(DEFAULT_ACCOUNT_COUNT);
+        if count == 0 {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("accounts.count"),
+                reason: String::from("must be at least 1"),
+            });
+        }
+
+        let result_count = self
+            .brave
+            .result_count
+            .unwrap_or(DEFAULT_BRAVE_RESULT_COUNT);
+        if !(1..=MAX_BRAVE_RESULT_COUNT).contains(&result_count) {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("brave.result_count"),
+                reason: format!("must be between 1 and {MAX_BRAVE_RESULT_COUNT}"),
+            });
+        }
+
+        let uniswap = UniswapConfig {
+            slippage_bps: self
+                .uniswap
+                .slippage_bps
+                .unwrap_or(DEFAULT_UNISWAP_SLIPPAGE_BPS),
+            max_slippage_bps: self
+                .uniswap
+                .max_slippage_bps
+                .unwrap_or(DEFAULT_UNISWAP_MAX_SLIPPAGE_BPS),
+            deadline_secs: self
+                .uniswap
+                .deadline_secs
+                .unwrap_or(DEFAULT_UNISWAP_DEADLINE_SECS),
+        };
+        if uniswap.max_slippage_bps >= MAX_BPS {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("uniswap.max_slippage_bps"),
+                reason: format!("must be below {MAX_BPS} (100%)"),
+            });
+        }
+        if uniswap.slippage_bps > uniswap.max_slippage_bps {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("uniswap.slippage_bps"),
+                reason: format!(
+                    "must not exceed uniswap.max_slippage_bps ({})",
+                    uniswap.max_slippage_bps
+                ),
+            });
+        }
+        if uniswap.deadline_secs == 0 {
+            problems.push(ConfigProblem::Invalid {
+                field: String::from("uniswap.deadline_secs"),
+                reason: String::from("must be at least 1"),
+            });
+        }
+
+        // Optional tool groups, only enabled when their configuration is present
+        let brave = non_empty(self.brave.api_key).map(|api_key| BraveConfig {
+            api_key,
+            result_count,
+        });
+        let zero_x = non_empty(self.zero_x.api_key).map(|api_key| ZeroXConfig { api_key });
+
+        if !problems.is_empty() {
+            return Err(ConfigError { problems });
+        }
+
+        Ok(Config {
+            server: ServerConfig {
+                address: self
+                    .server
+                    .address
+                    .unwrap_or_else(|| String::from(DEFAULT_SERVER_ADDRESS)),
+                port: self.server.port.unwrap_or(DEFAULT_SERVER_PORT),
+            },
+            default_chain,
+            chains,
+            accounts: AccountsConfig { mnemonic, count },
+            brave,
+            zero_x,
+            uniswap,
+        })
+    }
+}
+
+/// Resolves the `[[chains]]` entries, with the `eth` section configuring the default chain.
+fn resolve_chains(
+    eth: EthLayer,
+    mut layers: Vec<ChainLayer>,
+    problems: &mut Vec<ConfigProblem>,
+) -> (String, Vec<ChainConfig>) {
+    let default_chain = non_empty(eth.chain).unwrap_or_else(|| String::from(DEFAULT_CHAIN));
+
+    // The eth section overrides the RPC URL of a matching entry, or adds the default chain
+    match layers
+        .iter_mut()
+        .find(|layer| layer.name.eq_ignore_ascii_case(&default_chain))
+    {
+        Some(layer) => layer.rpc_url = eth.rpc_url.or(layer.rpc_url.take()),
+        None => layers.insert(
+            0,
+            ChainLayer {
+                name: default_chain.clone(),
+                rpc_url: eth.rpc_url,
+                ..Default::default()
+            },
+        ),
+    }
+
+    let mut chains: Vec<ChainConfig> = Vec::with_capacity(layers.len());
+    for (i, layer) in layers.into_iter().enumerate() {
+        if layer.name.trim().is_empty() {
+            problems.push(ConfigProblem::Missing {
+                field: format!("chains[{i}].name"),
+                env: None,
+            });
+            continue;
+        }
+        let is_default = layer.name.eq_ignore_ascii_case(&default_chain);
+        let Some(chain) = layer.resolve(is_default, problems) else {
+            continue;
+        };
+        if chains.iter().any(|other| {
+            other.name.eq_ignore_ascii_case(&chain.name) || other.chain_id == chain.chain_id
+        }) {
+            problems.push(ConfigProblem::Invalid {
+                field: format!("chains.{}", chain.name),
+                reason: String::from(
+                    "a chain with the same name or chain id is already configured",
+                ),
+            });
+            continue;
+        }
+        chains.push(chain);
+    }
+
+    (default_chain, chains)
+}
+
+/// Treats blank values the same as unset ones
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Overrides `target` with a parsed environment variable, recording a problem if it doesn't parse
+fn parse_env<T>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    target: &mut Option<T>,
+    field: &'static str,
+    env: &'static str,
+    problems: &mut Vec<ConfigProblem>,
+) where
+    T: FromStr,
+    T::Err: Display,
+{
+    if let Some(value) = lookup(env) {
+        match value.parse() {
+            Ok(parsed) => *target = Some(parsed),
+            Err(e) => problems.push(ConfigProblem::Invalid {
+                field: field.to_string(),
+                reason: format!("{env}={value:?} is invalid: {e}"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(toml: &str, env: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut problems = Vec::new();
+        let mut layer = ConfigLayer::from_toml(toml).unwrap();
+        layer.apply_env(|name| env.get(name).cloned(), &mut problems);
+        layer.validate(problems)
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let toml = r#"
+            [server]
+            port = 9000
+
+            [eth]
+            rpc_url = "http://file:8545"
+
+            [[chains]]
+            name = "base"
+            rpc_url = "http://base:8545"
+
+            [brave]
+            api_key = "file-brave"
+            result_count = 5
+
+            [zero_x]
+            api_key = "file-zero-x"
+        "#;
+        let cfg = load(toml, &[(ENV_ETH_RPC, "http://env:8545")]).unwrap();
+
+        assert_eq!(cfg.default_chain, "mainnet");
+        assert_eq!(cfg.chains.len(), 2);
+        assert_eq!(cfg.chains[0].rpc_url, "http://env:8545");
+        assert_eq!(cfg.chains[1].chain_id, 8453);
+        let brave = cfg.brave.unwrap();
+        assert_eq!(brave.api_key, "file-brave");
+        assert_eq!(brave.result_count, 5);
+        assert_eq!(cfg.server.bind_address(), "127.0.0.1:9000");
+        assert_eq!(cfg.accounts.mnemonic, ANVIL_MNEMONIC);
+        assert_eq!(cfg.accounts.count, DEFAULT_ACCOUNT_COUNT);
+    }
+
+    #[test]
+    fn test_only_rpc_url_is_required() {
+        let cfg = load("", &[(ENV_ETH_RPC, "http://localhost:8545")]).unwrap();
+
+        assert!(cfg.brave.is_none());
+        assert!(cfg.zero_x.is_none());
+        assert_eq!(cfg.chains.len(), 1);
+        assert_eq!(cfg.uniswap.slippage_bps, DEFAULT_UNISWAP_SLIPPAGE_BPS);
+    }
+
+    #[test]
+    fn test_slippage_must_not_exceed_cap() {
+        let toml = r#"
+            [uniswap]
+            max_slippage_bps = 100
+        "#;
+        let err = load(
+            toml,
+            &[
+                (ENV_ETH_RPC, "http://localhost:8545"),
+                (ENV_UNISWAP_SLIPPAGE_BPS, "200"),
+            ],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.problems.len(), 1);
+        assert!(matches!(
+            &err.problems[0],
+            ConfigProblem::Invalid { field, .. } if field == "uniswap.slippage_bps"
+        ));
+    }
+
+    #[test]
+    fn test_reports_every_problem() {
+        let toml = r#"
+            [[chains]]
+            name = "devnet"
+            rpc_url = "http://localhost:8545"
+            uniswap_router = "not-an-address"
+        "#;
+        let err = load(toml, &[(ENV_SERVER_PORT, "not-a-port")]).unwrap_err();
+        let fields: Vec<&str> = err
+            .problems
+            .iter()
+            .map(|p| match p {
+                ConfigProblem::Missing { field, .. } | ConfigProblem::Invalid { field, .. } => {
+                    field.as_str()
+                }
+                ConfigProblem::File { .. } => "file",
+            })
+            .collect();
+
+        assert_eq!(fields.len(), 4);
+        assert!(fields.contains(&"server.port"));
+        assert!(fields.contains(&"eth.rpc_url"));
+        assert!(fields.contains(&"chains.devnet.chain_id"));
+        assert!(fields.contains(&"chains.devnet.uniswap_router"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_fields() {
+        assert!(ConfigLayer::from_toml("[eth]\nrpc = \"http://localhost:8545\"").is_err());
+    }
+}
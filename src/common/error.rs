@@ -0,0 +1,226 @@
+//! # Error Module
+//!
+//! Errors tools report back to the agent. Tools keep returning `anyhow::Result`, failures the agent
+//! can act on are raised as a [`ToolError`] so the server can downcast them and reply with the
+//! matching MCP error code and a structured `data` payload:
+//!
+//! | Kind                 | MCP error        | Retryable                        |
+//! |----------------------|------------------|----------------------------------|
+//! | `invalid_params`     | `invalid_params` | no, the input must be fixed      |
+//! | `not_found`          | `invalid_params` | no, the input must be fixed      |
+//! | `insufficient_funds` | `invalid_params` | no, the amount must be lowered   |
+//! | `reverted`           | `internal_error` | no, the revert reason says why   |
+//! | `upstream`           | `internal_error` | on 429, 5xx and transport errors |
+//! | `rpc_timeout`        | `internal_error` | yes                              |
+//!
+//! RPC failures from ethers are classified with [`ToolError::from_rpc`] and
+//! [`ToolError::from_contract`], which decode revert reasons and recognize timeouts and funding
+//! errors. Anything else is passed through unchanged and reported as an internal error.
+use ethers::abi::AbiDecode;
+use ethers::contract::{ContractError, EthError};
+use ethers::prelude::{Bytes, Middleware, MiddlewareError, U256};
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+/// Selector of the `Panic(uint256)` error raised by failed asserts and arithmetic checks
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// A tool failure the agent can act on, serialized as the `data` of the MCP error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ToolError {
+    /// A parameter is malformed, e.g. an address or amount that doesn't parse
+    InvalidParams { field: String, reason: String },
+    /// A name given by the agent doesn't match anything, e.g. a chain or ENS name
+    NotFound {
+        what: String,
+        name: String,
+        detail: Option<String>,
+    },
+    /// The account can't cover the amount plus gas, amounts are in wei when known
+    InsufficientFunds {
+        required: Option<String>,
+        available: Option<String>,
+    },
+    /// Execution reverted, with the decoded reason when there is one
+    Reverted {
+        reason: Option<String>,
+        data: Option<String>,
+    },
+    /// An external API failed, without a status when the request didn't get a response
+    Upstream {
+        service: String,
+        status: Option<u16>,
+        message: String,
+    },
+    /// The RPC node didn't answer in time
+    RpcTimeout { message: String },
+}
+
+impl ToolError {
+    pub fn invalid_params(field: &str, reason: impl Display) -> Self {
+        ToolError::InvalidParams {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn not_found(what: &str, name: impl Display) -> Self {
+        ToolError::NotFound {
+            what: what.to_string(),
+            name: name.to_string(),
+            detail: None,
+        }
+    }
+
+    /// Whether the same call may succeed when retried unchanged
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ToolError::Upstream { status, .. } => {
+                status.is_none_or(|status| status == 429 || status >= 500)
+            }
+            ToolError::RpcTimeout { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the agent has to change its input, as opposed to a failure on the server side
+    pub fn is_caller_error(&self) -> bool {
+        matches!(
+            self,
+            ToolError::InvalidParams { .. }
+                | ToolError::NotFound { .. }
+                | ToolError::InsufficientFunds { .. }
+        )
+    }
+
+    /// Classifies a failed RPC request, unrecognized errors are returned as is
+    pub fn from_rpc<E: MiddlewareError>(e: E) -> anyhow::Error {
+        if let Some(response) = e.as_error_response() {
+            if response.is_revert() {
+                return Self::reverted(response.as_revert_data()).into();
+            }
+            if response.message.contains("insufficient funds") {
+                return ToolError::InsufficientFunds {
+                    required: None,
+                    available: None,
+                }
+                .into();
+            }
+        }
+
+        let message = e.to_string();
+        if message.contains("timed out") {
+            return ToolError::RpcTimeout { message }.into();
+        }
+        anyhow::Error::msg(message)
+    }
+
+    /// Classifies a failed contract call, decoding the revert reason when it reverted
+    pub fn from_contract<M: Middleware>(e: ContractError<M>) -> anyhow::Error {
+        match e {
+            ContractError::Revert(data) => Self::reverted(Some(data)).into(),
+            ContractError::MiddlewareError { e } => Self::from_rpc(e),
+            ContractError::ProviderError { e } => Self::from_rpc(e),
+            e => anyhow::Error::msg(e.to_string()),
+        }
+    }
+
+    /// Builds a revert error, decoding `Error(string)` and `Panic(uint256)` reasons
+    fn reverted(data: Option<Bytes>) -> Self {
+        let data = data.filter(|data| !data.is_empty());
+        let reason = data.as_ref().and_then(|data| {
+            if let Some(reason) = String::decode_with_selector(data) {
+                return Some(reason);
+            }
+            let code = data.strip_prefix(&PANIC_SELECTOR)?;
+            U256::decode(code)
+                .ok()
+                .map(|code| format!("panic code {code:#x}"))
+        });
+        ToolError::Reverted {
+            reason,
+            data: data.map(|data| data.to_string()),
+        }
+    }
+}
+
+impl Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToolError::InvalidParams { field, reason } => write!(f, "invalid {field}: {reason}"),
+            ToolError::NotFound { what, name, detail } => {
+                write!(f, "{what} {name} not found")?;
+                if let Some(detail) = detail {
+                    write!(f, ", {detail}")?;
+                }
+                Ok(())
+            }
+            ToolError::InsufficientFunds {
+                required,
+                available,
+            } => match (required, available) {
+                (Some(required), Some(available)) => write!(
+                    f,
+                    "insufficient funds: {required} wei required, {available} wei available"
+                ),
+                _ => f.write_str("insufficient funds for gas * price + value"),
+            },
+            ToolError::Reverted { reason, data } => match (reason, data) {
+                (Some(reason), _) => write!(f, "execution reverted: {reason}"),
+                (None, Some(data)) => write!(f, "execution reverted with data {data}"),
+                (None, None) => f.write_str("execution reverted"),
+            },
+            ToolError::Upstream {
+                service,
+                status,
+                message,
+            } => match status {
+                Some(status) => write!(f, "{service} API error {status}: {message}"),
+                None => write!(f, "{service} API request failed: {message}"),
+            },
+            ToolError::RpcTimeout { message } => write!(f, "RPC request timed out: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_revert_reasons() {
+        let error_string: Bytes = "0x08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000174d756c746963616c6c333a2063616c6c206661696c6564000000000000000000".parse().unwrap();
+        let panic: Bytes =
+            "0x4e487b710000000000000000000000000000000000000000000000000000000000000011"
+                .parse()
+                .unwrap();
+
+        let ToolError::Reverted { reason, .. } = ToolError::reverted(Some(error_string)) else {
+            panic!("expected a revert");
+        };
+        assert_eq!(reason.as_deref(), Some("Multicall3: call failed"));
+
+        let ToolError::Reverted { reason, .. } = ToolError::reverted(Some(panic)) else {
+            panic!("expected a revert");
+        };
+        assert_eq!(reason.as_deref(), Some("panic code 0x11"));
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let upstream = |status| ToolError::Upstream {
+            service: String::from("0x"),
+            status,
+            message: String::new(),
+        };
+
+        assert!(upstream(Some(503)).is_retryable());
+        assert!(upstream(Some(429)).is_retryable());
+        assert!(upstream(None).is_retryable());
+        assert!(!upstream(Some(400)).is_retryable());
+        assert!(!ToolError::invalid_params("amount", "not a number").is_retryable());
+    }
+}
@@ -1,19 +1,24 @@
 pub mod accounts;
+pub mod chains;
+pub mod cli;
 pub mod context;
+pub mod error;
+pub mod tokens;
 
 const ENV_SERVER_ADDRESS: &str = "MCP_SERVER_ADDRESS";
 const ENV_SERVER_PORT: &str = "MCP_SERVER_PORT";
 const ENV_ETH_RPC: &str = "ETH_RPC";
+const ENV_ETH_CHAIN: &str = "ETH_CHAIN";
+const ENV_ACCOUNTS_MNEMONIC: &str = "ACCOUNTS_MNEMONIC";
+const ENV_ACCOUNTS_COUNT: &str = "ACCOUNTS_COUNT";
 const ENV_BRAVE_API_KEY: &str = "BRAVE_API_KEY";
+const ENV_BRAVE_RESULT_COUNT: &str = "BRAVE_RESULT_COUNT";
 const ENV_ZERO_X_API_KEY: &str = "ZERO_X_API_KEY";
+const ENV_UNISWAP_SLIPPAGE_BPS: &str = "UNISWAP_SLIPPAGE_BPS";
+const ENV_UNISWAP_MAX_SLIPPAGE_BPS: &str = "UNISWAP_MAX_SLIPPAGE_BPS";
+const ENV_UNISWAP_DEADLINE_SECS: &str = "UNISWAP_DEADLINE_SECS";
 
 pub fn get_env_var(name: &str) -> anyhow::Result<String> {
     let var = std::env::var(name)?;
     Ok(var)
 }
-
-pub fn get_bind_address() -> anyhow::Result<String> {
-    let addr = get_env_var(ENV_SERVER_ADDRESS)?;
-    let port = get_env_var(ENV_SERVER_PORT)?;
-    Ok(format!("{addr}:{port}"))
-}
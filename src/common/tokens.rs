@@ -0,0 +1,158 @@
+//! # Tokens Module
+//!
+//! Token lists in the [Uniswap token list](https://tokenlists.org/) format, loaded per chain from
+//! the `token_list` field of a chain entry. A list may hold tokens of several chains, only those
+//! whose `chainId` matches the chain are kept.
+//!
+//! Lists let token inputs take a symbol such as `USDC` instead of a contract address, and back the
+//! token search tool. Symbols are matched case-insensitively, a symbol shared by several tokens of
+//! the same chain is ambiguous and has to be replaced by the address.
+use ethers::prelude::Address;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A token of a token list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub(crate) chain_id: u64,
+    pub(crate) address: Address,
+    pub(crate) name: String,
+    pub(crate) symbol: String,
+    pub(crate) decimals: u8,
+}
+
+/// A token list file, other fields of the format (version, logos, tags...) are ignored.
+#[derive(Debug, Deserialize)]
+struct TokenListFile {
+    name: String,
+    tokens: Vec<TokenInfo>,
+}
+
+/// Tokens of a single chain, from the chain's token list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenList {
+    pub(crate) name: Option<String>,
+    pub(crate) tokens: Vec<TokenInfo>,
+}
+
+impl TokenList {
+    /// Reads a token list file, keeping the tokens of the chain
+    pub(crate) fn load(path: &Path, chain_id: u64) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::parse(&contents, chain_id)
+    }
+
+    fn parse(contents: &str, chain_id: u64) -> Result<Self, String> {
+        let file: TokenListFile =
+            serde_json::from_str(contents).map_err(|e| format!("not a valid token list: {e}"))?;
+
+        let mut tokens: Vec<TokenInfo> = Vec::new();
+        for token in file.tokens {
+            // Lists may repeat a token, the first entry wins
+            if token.chain_id == chain_id && !tokens.iter().any(|t| t.address == token.address) {
+                tokens.push(token);
+            }
+        }
+        Ok(Self {
+            name: Some(file.name),
+            tokens,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Tokens whose symbol matches, ignoring case
+    pub fn by_symbol(&self, symbol: &str) -> Vec<&TokenInfo> {
+        let symbol = symbol.trim();
+        self.tokens
+            .iter()
+            .filter(|token| token.symbol.eq_ignore_ascii_case(symbol))
+            .collect()
+    }
+
+    /// Tokens whose symbol or name contains the query, ignoring case. Exact symbol matches come
+    /// first, then symbols starting with the query, then the other matches in list order.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&TokenInfo> {
+        let query = query.trim().to_lowercase();
+        let mut matches: Vec<(u8, &TokenInfo)> = self
+            .tokens
+            .iter()
+            .filter_map(|token| {
+                let symbol = token.symbol.to_lowercase();
+                let rank = if symbol == query {
+                    0
+                } else if symbol.starts_with(&query) {
+                    1
+                } else if symbol.contains(&query) || token.name.to_lowercase().contains(&query) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, token))
+            })
+            .collect();
+        // Stable sort, tokens of the same rank keep the list order
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, token)| token)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = r#"{
+        "name": "Test List",
+        "timestamp": "2024-01-01T00:00:00.000Z",
+        "version": { "major": 1, "minor": 0, "patch": 0 },
+        "tokens": [
+            { "chainId": 1, "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "name": "USD Coin", "symbol": "USDC", "decimals": 6, "logoURI": "https://example.org/usdc.png" },
+            { "chainId": 1, "address": "0x6B175474E89094C44Da98b954EedeAC495271d0F", "name": "Dai Stablecoin", "symbol": "DAI", "decimals": 18 },
+            { "chainId": 1, "address": "0x6B175474E89094C44Da98b954EedeAC495271d0F", "name": "Dai", "symbol": "DAI", "decimals": 18 },
+            { "chainId": 1, "address": "0x1111111111111111111111111111111111111111", "name": "Bridged USD Coin", "symbol": "usdc", "decimals": 6 },
+            { "chainId": 1, "address": "0x2222222222222222222222222222222222222222", "name": "USDC Yield", "symbol": "yUSDC", "decimals": 6 },
+            { "chainId": 8453, "address": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "name": "USD Coin", "symbol": "USDC", "decimals": 6 }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_keeps_chain_tokens() {
+        let list = TokenList::parse(LIST, 1).unwrap();
+
+        assert_eq!(list.name.as_deref(), Some("Test List"));
+        assert_eq!(list.tokens.len(), 4);
+        assert_eq!(list.by_symbol("dai").len(), 1);
+        assert_eq!(list.by_symbol(" USDC ").len(), 2);
+        assert!(list.by_symbol("WETH").is_empty());
+        assert_eq!(
+            TokenList::parse(LIST, 8453)
+                .unwrap()
+                .by_symbol("USDC")
+                .len(),
+            1
+        );
+        assert!(TokenList::parse(LIST, 10).unwrap().is_empty());
+        assert!(TokenList::parse("{}", 1).is_err());
+    }
+
+    #[test]
+    fn test_search_ranks_symbols_first() {
+        let list = TokenList::parse(LIST, 1).unwrap();
+        let symbols: Vec<&str> = list
+            .search("usdc", 10)
+            .iter()
+            .map(|token| token.symbol.as_str())
+            .collect();
+
+        assert_eq!(symbols, ["USDC", "usdc", "yUSDC"]);
+        assert_eq!(list.search("coin", 10).len(), 3);
+        assert_eq!(list.search("usdc", 1).len(), 1);
+    }
+}
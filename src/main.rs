@@ -3,18 +3,33 @@ mod tools;
 
 use tools::agent_mcp::AgentMcpServer;
 
-use crate::common::get_bind_address;
+use crate::common::cli::{Cli, Transport};
+use crate::common::context::{Config, Context};
+use crate::tools::MultiTool;
 use anyhow::Result;
+use clap::Parser;
+use rmcp::ServiceExt;
 use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use rmcp::transport::stdio;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use std::sync::Arc;
 use tracing_subscriber::{self, EnvFilter};
 
+const SSE_PATH: &str = "/sse";
+const SSE_POST_PATH: &str = "/message";
+const STREAMABLE_HTTP_PATH: &str = "/mcp";
+
 /// Main entry point for MCP Server.
 ///
 /// This is very similar to the examples from the documentation for simplicity and to ensure it is
 /// set up correctly.
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
+    let cli = Cli::parse();
+
+    // Initialize logging, always on stderr so it never mixes with the stdio protocol stream
     tracing_subscriber::fmt()
         .with_env_filter(
             EnvFilter::from_default_env()
@@ -26,34 +41,74 @@ async fn main() -> Result<()> {
         .init();
 
     tracing::info!("Starting MCP server with tool groups");
+
+    // Server state is built once and shared by every session
+    let cfg = Config::load(cli.config.as_deref())?;
+    let ctx = Arc::new(Context::new(MultiTool::new(&cfg)?));
+
     tracing::info!("Available tool groups:");
-    tracing::info!("eth_tools | brave_tools | zero_x_tools | uniswap_tools");
+    tracing::info!("{}", ctx.m_tool.enabled_groups_summary());
+
+    match cli.transport {
+        Transport::Http => serve_http(&cfg, ctx).await,
+        Transport::Stdio => serve_stdio(ctx).await,
+    }
+}
+
+/// Serves the MCP server over HTTP until ctrl-c is received.
+///
+/// A single axum router serves both the streamable HTTP transport and the legacy HTTP+SSE
+/// transport, with every session created from the same `AgentMcpServer` factory.
+async fn serve_http(cfg: &Config, ctx: Arc<Context<MultiTool>>) -> Result<()> {
+    let factory = move || AgentMcpServer::new(ctx.clone());
 
     let config = SseServerConfig {
-        bind: get_bind_address()?.parse()?,
-        sse_path: String::from("/sse"),
-        post_path: String::from("/message"),
+        bind: cfg.server.bind_address().parse()?,
+        sse_path: String::from(SSE_PATH),
+        post_path: String::from(SSE_POST_PATH),
         ct: tokio_util::sync::CancellationToken::new(),
         sse_keep_alive: None,
     };
 
     let (sse_server, router) = SseServer::new(config);
+
+    // Streamable HTTP sessions are stateful so clients get session ids and can resume streams
+    let session_factory = factory.clone();
+    let streamable_http = StreamableHttpService::new(
+        move || Ok(session_factory()),
+        LocalSessionManager::default().into(),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = router.nest_service(STREAMABLE_HTTP_PATH, streamable_http);
+
     let listener = tokio::net::TcpListener::bind(sse_server.config.bind).await?;
     let ct = sse_server.config.ct.child_token();
 
     let server = axum::serve(listener, router).with_graceful_shutdown(async move {
         ct.cancelled().await;
-        tracing::info!("sse server cancelled");
+        tracing::info!("http server cancelled");
     });
     tokio::spawn(async move {
         if let Err(e) = server.await {
-            tracing::error!(error = %e, "sse server shutdown with error");
+            tracing::error!(error = %e, "http server shutdown with error");
         }
     });
 
-    let ct = sse_server.with_service(AgentMcpServer::new);
+    let ct = sse_server.with_service(factory);
 
     tokio::signal::ctrl_c().await?;
     ct.cancel();
     Ok(())
 }
+
+/// Serves a single client over stdin/stdout until the client disconnects.
+async fn serve_stdio(ctx: Arc<Context<MultiTool>>) -> Result<()> {
+    tracing::info!("Serving MCP over stdio");
+    let service = AgentMcpServer::new(ctx)
+        .serve(stdio())
+        .await
+        .inspect_err(|e| tracing::error!(error = %e, "stdio server failed to start"))?;
+
+    service.waiting().await?;
+    Ok(())
+}
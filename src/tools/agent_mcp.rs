@@ -8,9 +8,14 @@
 //! # Architecture
 //!
 //! - **AgentMcpServer**: Main server struct implementing the MCP ServerHandler protocol
-//! - **Thread-safe context**: Shared state management using Arc<Mutex<Context<MultiTool>>>
-//! - **Tool routing**: Automatic tool discovery and routing using procedural macros
-//! - **Error handling**: Standardized MCP error responses with detailed context
+//! - **Thread-safe context**: Shared state using Arc<Context<MultiTool>>, tools run concurrently
+//!   and only transactions from the same signing account are serialized
+//! - **Tool routing**: Automatic tool discovery and routing using procedural macros, with one
+//!   router per tool group so only configured groups are exposed
+//! - **Structured results**: Tools declare an output schema and reply with structured content
+//!   plus a human-readable text block
+//! - **Error handling**: Failures are mapped to `invalid_params` or `internal_error` with a
+//!   structured `data` payload telling the agent what went wrong and whether to retry
 //!
 //! # Available Tools
 //!
@@ -19,14 +24,34 @@
 //! - **`send`**: Transfer ETH between addresses with transaction confirmation
 //! - **`get_contract`**: Verify contract deployment and inspect bytecode
 //! - **`get_erc20_balance`**: Check ERC20 token balances
+//! - **`transfer_erc20`**: Transfer ERC20 tokens from a managed account
+//! - **`approve_erc20`**: Approve a spender for an exact amount of tokens
+//! - **`get_erc20_allowance`**: Check the allowance of a spender
+//! - **`revoke_erc20_approval`**: Set the allowance of a spender back to zero
+//! - **`list_chains`**: List the configured chains, every EVM, Uniswap and 0x tool takes an
+//!   optional `chain` selecting one of them
 //!
 //! ## DeFi Protocol Integration
 //! - **`get_quote`**: Get swap quotes from 0x Protocol aggregator
+//! - **`swap_0x`**: Execute swaps from firm 0x quotes, signing their Permit2 message
 //! - **`swap_eth_for_tokens`**: Execute ETH-to-token swaps via Uniswap V2
 //! - **`swap_tokens_for_eth`**: Execute token-to-ETH swaps via Uniswap V2
+//! - **`swap_tokens_for_tokens`**: Execute token-to-token swaps via Uniswap V2, multi-hop or
+//!   routed through WETH
+//! - **`swap_exact_output`**: Buy an exact amount of ETH or tokens via Uniswap V2, selling at most
+//!   a maximum input
+//! - **`get_uniswap_quote`**: Quote a swap on-chain with the Uniswap V2 router
+//! - **`swap_uniswap_v3`**: Execute exact input or exact output swaps via Uniswap V3 pools
+//! - **`get_uniswap_v3_quote`**: Quote a swap on-chain with the Uniswap V3 QuoterV2, picking fee
+//!   tiers
 //!
 //! ## Web Search
 //! - **`web_search`**: Search for contract addresses and blockchain information
+//! - **`general_web_search`**: Search the web for news, documentation and protocol research
+//!
+//! ## Token Lists
+//! - **`search_tokens`**: Search a chain's token list by symbol or name, token inputs of the other
+//!   tools accept the symbols it returns
 //!
 //! # Usage
 //!
@@ -47,8 +72,8 @@
 //! # Server Capabilities
 //!
 //! - **Tools**: All blockchain and DeFi operations
-//! - **Prompts**: Standardized prompt templates (enabled for future use)
-//! - **Resources**: Access to blockchain data and contract information
+//! - **Prompts**: Vetted multi-step procedures for common workflows, see [`prompts`]
+//! - **Resources**: Managed accounts, chain head and token metadata, see [`resources`]
 //! - **Protocol**: Full MCP protocol compliance with latest version support
 //!
 //! # Key Features
@@ -64,95 +89,190 @@
 //! Built on the `rmcp` (Rust MCP) framework with integration to `ethers` for blockchain
 //! operations, providing a robust foundation for DeFi automation and blockchain tooling.
 use anyhow::Result;
-use rmcp::handler::server::tool::{Parameters, ToolRouter};
+use rmcp::handler::server::common::cached_schema_for_type;
+use rmcp::handler::server::router::{prompt::PromptRouter, tool::ToolRouter};
+use rmcp::handler::server::wrapper::Parameters;
 use rmcp::model::{Implementation, ProtocolVersion};
-use rmcp::{ServerHandler, model::*, tool, tool_handler, tool_router};
+use rmcp::service::RequestContext;
+use rmcp::{RoleServer, ServerHandler, model::*, prompt_handler, tool, tool_handler, tool_router};
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Display;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
-use crate::common::context::{Config, Context};
-use crate::tools::MultiTool;
-use crate::tools::traits::{BraveTools, EvmTools, UniSwapTools, ZeroXTools};
+use crate::common::context::Context;
+use crate::common::error::ToolError;
+use crate::tools::brave_tools::{AddressSearchResult, GeneralSearchResult};
+use crate::tools::erc20_tools::{AllowanceResult, TokenTxResult};
+use crate::tools::eth_tools::{ChainList, ContractResult};
+use crate::tools::resources::{self, ResourceUri};
+use crate::tools::results::{BalanceResult, SwapResult, TxResult};
+use crate::tools::token_list_tools::TokenSearchResult;
+use crate::tools::traits::{
+    BraveTools, Erc20Tools, EvmTools, ResourceTools, TokenListTools, UniSwapTools, UniSwapV3Tools,
+    ZeroXTools,
+};
+use crate::tools::uniswap_tools::UniswapQuoteResult;
+use crate::tools::uniswap_v3_tools::UniswapV3QuoteResult;
+use crate::tools::zero_x_tools::{QuoteResult, ZeroXSwapResult};
+use crate::tools::{MultiTool, ToolGroup};
 
 // Main server struct that implements ServerHandler
+//
+// One instance is created per client session. The context is built once at startup and shared by
+// every session, anything session specific is layered on top of it in this struct.
 #[derive(Clone)]
 pub struct AgentMcpServer {
-    // Internal state - Contains server context, behind Atomic Reference and Mutex for thread safety
-    pub(crate) ctx: Arc<Mutex<Context<MultiTool>>>,
+    // Internal state - Contains server context, behind Atomic Reference. Tools take no global lock,
+    // write operations are serialized per signing account inside the MultiTool.
+    pub(crate) ctx: Arc<Context<MultiTool>>,
     // Tool Router
     tool_router: ToolRouter<AgentMcpServer>,
+    // Prompt Router, see the prompts module
+    prompt_router: PromptRouter<AgentMcpServer>,
 }
 
-#[tool_router]
 impl AgentMcpServer {
-    pub fn new() -> Self {
-        let cfg = Config::new();
-        let m_tool = MultiTool::new(&cfg);
+    pub fn new(ctx: Arc<Context<MultiTool>>) -> Self {
+        // Only route the tool groups that are configured, and the prompts relying on them
+        let groups = ctx.m_tool.enabled_groups();
+        let tool_router = groups
+            .iter()
+            .copied()
+            .map(Self::group_router)
+            .fold(ToolRouter::new(), |router, group| router + group);
+        let prompt_router = Self::enabled_prompt_router(&groups);
 
         AgentMcpServer {
-            ctx: Arc::new(Mutex::new(Context::new(m_tool))),
-            tool_router: Self::tool_router(),
+            ctx,
+            tool_router,
+            prompt_router,
         }
     }
 
+    fn group_router(group: ToolGroup) -> ToolRouter<Self> {
+        match group {
+            ToolGroup::Eth => Self::eth_tool_router(),
+            ToolGroup::Brave => Self::brave_tool_router(),
+            ToolGroup::ZeroX => Self::zero_x_tool_router(),
+            ToolGroup::Uniswap => Self::uniswap_tool_router(),
+            ToolGroup::UniswapV3 => Self::uniswap_v3_tool_router(),
+            ToolGroup::TokenList => Self::token_list_tool_router(),
+        }
+    }
+}
+
+/// Builds a tool result carrying the typed result as structured content, along with its
+/// human-readable form as a text block for clients that don't read structured content.
+fn structured_result<T: Serialize + Display>(
+    result: T,
+) -> std::result::Result<CallToolResult, ErrorData> {
+    let value = serde_json::to_value(&result).map_err(|e| {
+        ErrorData::internal_error(format!("failed to serialize tool result: {e}"), None)
+    })?;
+    let mut reply = CallToolResult::structured(value);
+    reply.content = vec![Content::text(result.to_string())];
+    Ok(reply)
+}
+
+/// Maps a tool failure to an MCP error.
+///
+/// [`ToolError`]s become `invalid_params` when the agent has to change its input and
+/// `internal_error` otherwise, with the error and whether it's retryable as the `data` payload.
+/// Any other failure is an internal error.
+fn tool_error(action: &str, e: anyhow::Error) -> ErrorData {
+    let message = format!("{action}: {e}");
+    let Some(error) = e.downcast_ref::<ToolError>() else {
+        return ErrorData::internal_error(message, Some(json!({ "kind": "internal" })));
+    };
+
+    let mut data = serde_json::to_value(error).unwrap_or_default();
+    data["retryable"] = error.is_retryable().into();
+    if error.is_caller_error() {
+        ErrorData::invalid_params(message, Some(data))
+    } else {
+        ErrorData::internal_error(message, Some(data))
+    }
+}
+
+/// Builds a resource read result holding the value as JSON.
+fn json_resource<T: Serialize>(
+    uri: String,
+    value: &T,
+) -> std::result::Result<ReadResourceResult, ErrorData> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| {
+        ErrorData::internal_error(format!("failed to serialize resource: {e}"), None)
+    })?;
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents::TextResourceContents {
+            uri,
+            mime_type: Some(String::from("application/json")),
+            text,
+            meta: None,
+        }],
+    })
+}
+
+#[tool_router(router = eth_tool_router)]
+impl AgentMcpServer {
     // Balance command
-    #[tool(description = "Get the balance of an account in wei")]
+    #[tool(description = "Get the ETH balance of an account, in wei and formatted in ETH",
+        output_schema = cached_schema_for_type::<BalanceResult>())]
     async fn balance(
         &self,
         Parameters(address): Parameters<super::eth_tools::BalanceInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let balance = self
             .ctx
-            .lock()
-            .await
             .m_tool
-            .get_balance(address.addr)
+            .get_balance(address.addr, address.chain)
             .await
-            .map_err(|e| {
-                ErrorData::internal_error(format!("server failed to get balance: {e}"), None)
-            })?;
-        Ok(CallToolResult::success(vec![Content::text(balance)]))
+            .map_err(|e| tool_error("server failed to get balance", e))?;
+        structured_result(balance)
     }
 
     // Balance command
-    #[tool(description = "Sends an amount in ETH from one address to another")]
+    #[tool(description = "Sends ETH from a managed account to another address, amounts default to ether and `amount_unit` accepts wei, gwei or ether",
+        output_schema = cached_schema_for_type::<TxResult>())]
     async fn send(
         &self,
         Parameters(input): Parameters<super::eth_tools::SendInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let receipt = self
             .ctx
-            .lock()
-            .await
             .m_tool
-            .send(input.sender, input.receiver, input.amount)
+            .send(
+                input.sender,
+                input.receiver,
+                input.amount,
+                input.amount_unit,
+                input.chain,
+            )
             .await
-            .map_err(|e| ErrorData::internal_error(format!("server failed to send: {e}"), None))?;
-        Ok(CallToolResult::success(vec![Content::text(receipt)]))
+            .map_err(|e| tool_error("server failed to send", e))?;
+        structured_result(receipt)
     }
 
     // Verify whether a contract is deployed
-    #[tool(description = "Checks whether a contract is deployed given the address")]
+    #[tool(description = "Checks whether a contract is deployed given the address",
+        output_schema = cached_schema_for_type::<ContractResult>())]
     async fn get_contract(
         &self,
         Parameters(input): Parameters<super::eth_tools::GetContractInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
-            .await
             .m_tool
-            .get_contract(input.addr)
+            .get_contract(input.addr, input.chain)
             .await
-            .map_err(|e| {
-                ErrorData::internal_error(format!("server failed to get contract: {e}"), None)
-            })?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("server failed to get contract", e))?;
+        structured_result(reply)
     }
 
     // ERC20 Balance command
     #[tool(
-        description = "Gets the balance of an address for a specific erc20 token using its defined denominations"
+        description = "Gets the balance of an address for a specific erc20 token using its defined denominations",
+        output_schema = cached_schema_for_type::<BalanceResult>()
     )]
     async fn get_erc20_balance(
         &self,
@@ -160,87 +280,314 @@ impl AgentMcpServer {
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
+            .m_tool
+            .get_erc20_balance(input.erc20_addr, input.account, input.chain)
+            .await
+            .map_err(|e| tool_error("server failed to get erc20 balance", e))?;
+        structured_result(reply)
+    }
+
+    // ERC20 transfer command
+    #[tool(
+        description = "Transfers ERC20 tokens from a managed account, amounts default to the token's base units",
+        output_schema = cached_schema_for_type::<TokenTxResult>()
+    )]
+    async fn transfer_erc20(
+        &self,
+        Parameters(input): Parameters<super::erc20_tools::TokenTransferInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .transfer(input)
+            .await
+            .map_err(|e| tool_error("server failed to transfer tokens", e))?;
+        structured_result(reply)
+    }
+
+    // ERC20 approve command
+    #[tool(
+        description = "Allows a spender to move an exact amount of ERC20 tokens from a managed account, unlimited approvals must be requested explicitly",
+        output_schema = cached_schema_for_type::<TokenTxResult>()
+    )]
+    async fn approve_erc20(
+        &self,
+        Parameters(input): Parameters<super::erc20_tools::ApproveInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .approve(input)
+            .await
+            .map_err(|e| tool_error("server failed to approve tokens", e))?;
+        structured_result(reply)
+    }
+
+    // ERC20 allowance command
+    #[tool(
+        description = "Gets the amount of ERC20 tokens a spender may still move on behalf of an owner",
+        output_schema = cached_schema_for_type::<AllowanceResult>()
+    )]
+    async fn get_erc20_allowance(
+        &self,
+        Parameters(input): Parameters<super::erc20_tools::AllowanceInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .allowance(input)
             .await
+            .map_err(|e| tool_error("server failed to get allowance", e))?;
+        structured_result(reply)
+    }
+
+    // ERC20 revoke command
+    #[tool(
+        description = "Revokes the ERC20 allowance a managed account granted to a spender",
+        output_schema = cached_schema_for_type::<TokenTxResult>()
+    )]
+    async fn revoke_erc20_approval(
+        &self,
+        Parameters(input): Parameters<super::erc20_tools::AllowanceInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
             .m_tool
-            .get_erc20_balance(input.erc20_addr, input.account)
+            .revoke(input)
             .await
-            .map_err(|e| {
-                ErrorData::internal_error(format!("server failed to get erc20 balance: {e}"), None)
-            })?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("server failed to revoke allowance", e))?;
+        structured_result(reply)
     }
 
+    // List configured chains
+    #[tool(
+        description = "Lists the chains the server can operate on, any chain can be selected by name or chain id",
+        output_schema = cached_schema_for_type::<ChainList>()
+    )]
+    async fn list_chains(&self) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .list_chains()
+            .await
+            .map_err(|e| tool_error("server failed to list chains", e))?;
+        structured_result(reply)
+    }
+}
+
+#[tool_router(router = brave_tool_router)]
+impl AgentMcpServer {
     // Perform web search for contract addresses
-    #[tool(description = "Searches the web for different types of contract addresses")]
+    #[tool(description = "Searches the web for different types of contract addresses, annotating each address with the results mentioning it, whether it has code on-chain and its ERC20 symbol and decimals, most mentioned first",
+        output_schema = cached_schema_for_type::<AddressSearchResult>())]
     async fn web_search(
         &self,
         Parameters(input): Parameters<super::brave_tools::WebSearchInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
+            .m_tool
+            .search(input.query, input.chain)
             .await
+            .map_err(|e| tool_error("web search failed", e))?;
+        structured_result(reply)
+    }
+
+    // Perform a general web search
+    #[tool(description = "Searches the web for news, documentation or research, returning the title, URL, description and age of each result, with optional news and discussion results. Supports paging with `offset` and filtering by freshness, country and safe search",
+        output_schema = cached_schema_for_type::<GeneralSearchResult>())]
+    async fn general_web_search(
+        &self,
+        Parameters(input): Parameters<super::brave_tools::GeneralSearchInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
             .m_tool
-            .search(input.query)
+            .general_search(input)
             .await
-            .map_err(|e| ErrorData::internal_error(format!("web search failed: {e}"), None))?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("web search failed", e))?;
+        structured_result(reply)
     }
+}
 
+#[tool_router(router = zero_x_tool_router)]
+impl AgentMcpServer {
     // Get Swap quote from 0x Protocol
-    #[tool(description = "Gets a quote for a swap from one token type to another")]
+    #[tool(description = "Gets a quote for a swap from one token type to another, summarizing the amount bought, effective price, gas, fees, liquidity sources and the taker's allowance and balance issues",
+        output_schema = cached_schema_for_type::<QuoteResult>())]
     async fn get_quote(
         &self,
         Parameters(input): Parameters<super::zero_x_tools::QuoteInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
-            .await
             .m_tool
             .get_quote(input)
             .await
-            .map_err(|e| ErrorData::internal_error(format!("quote request failed: {e}"), None))?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("quote request failed", e))?;
+        structured_result(reply)
     }
 
+    // Swap through the 0x aggregator from a firm quote
+    #[tool(description = "Swaps tokens through the 0x aggregator for best execution, approving Permit2 and signing its message when selling a token",
+        output_schema = cached_schema_for_type::<ZeroXSwapResult>())]
+    async fn swap_0x(
+        &self,
+        Parameters(input): Parameters<super::zero_x_tools::ZeroXSwapInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .swap(input)
+            .await
+            .map_err(|e| tool_error("0x swap failed", e))?;
+        structured_result(reply)
+    }
+}
+
+#[tool_router(router = uniswap_tool_router)]
+impl AgentMcpServer {
     // Use uniswap tools to swap eth for another token type
-    #[tool(description = "Swaps ETH tokens for a specified output token")]
+    #[tool(description = "Swaps ETH tokens for a specified output token, quoting the expected output on-chain when `min_amount_out` is omitted",
+        output_schema = cached_schema_for_type::<SwapResult>())]
     async fn swap_eth_for_tokens(
         &self,
         Parameters(input): Parameters<super::uniswap_tools::SwapEthInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
-            .await
             .m_tool
             .swap_eth_to_token(input)
             .await
-            .map_err(|e| ErrorData::internal_error(format!("token swap failed: {e}"), None))?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("token swap failed", e))?;
+        structured_result(reply)
     }
 
     // Use uniswap tools to swap tokens for eth
-    #[tool(description = "Swaps specific tokens for eth")]
+    #[tool(description = "Swaps specific tokens for eth, approving the router for the exact amount when needed and quoting the expected output on-chain when `min_amount_out` is omitted",
+        output_schema = cached_schema_for_type::<SwapResult>())]
     async fn swap_tokens_for_eth(
         &self,
         Parameters(input): Parameters<super::uniswap_tools::SwapTokenInput>,
     ) -> std::result::Result<CallToolResult, ErrorData> {
         let reply = self
             .ctx
-            .lock()
-            .await
             .m_tool
             .swap_token_to_eth(input)
             .await
-            .map_err(|e| ErrorData::internal_error(format!("token swap failed: {e}"), None))?;
-        Ok(CallToolResult::success(vec![Content::text(reply)]))
+            .map_err(|e| tool_error("token swap failed", e))?;
+        structured_result(reply)
+    }
+
+    // Use uniswap tools to swap tokens for other tokens
+    #[tool(description = "Swaps specific tokens for other tokens, through the `via` tokens or through WETH when the tokens have no pair, approving the router for the exact amount when needed and quoting the expected output on-chain when `min_amount_out` is omitted",
+        output_schema = cached_schema_for_type::<SwapResult>())]
+    async fn swap_tokens_for_tokens(
+        &self,
+        Parameters(input): Parameters<super::uniswap_tools::SwapTokensInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .swap_tokens_to_tokens(input)
+            .await
+            .map_err(|e| tool_error("token swap failed", e))?;
+        structured_result(reply)
+    }
+
+    // Use uniswap tools to buy an exact amount of eth or tokens
+    #[tool(description = "Buys an exact amount of ETH or tokens with ETH or tokens, selling at most `max_amount_in`, which is quoted on-chain and raised by the slippage tolerance when omitted",
+        output_schema = cached_schema_for_type::<SwapResult>())]
+    async fn swap_exact_output(
+        &self,
+        Parameters(input): Parameters<super::uniswap_tools::SwapExactOutInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .swap_exact_output(input)
+            .await
+            .map_err(|e| tool_error("token swap failed", e))?;
+        structured_result(reply)
+    }
+
+    // Quote a swap on-chain with the uniswap router
+    #[tool(
+        description = "Quotes a swap on-chain with the Uniswap V2 router, for an exact input or output amount, with the pair reserves and price impact",
+        output_schema = cached_schema_for_type::<UniswapQuoteResult>()
+    )]
+    async fn get_uniswap_quote(
+        &self,
+        Parameters(input): Parameters<super::uniswap_tools::UniswapQuoteInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .quote(input)
+            .await
+            .map_err(|e| tool_error("uniswap quote failed", e))?;
+        structured_result(reply)
+    }
+}
+
+#[tool_router(router = uniswap_v3_tool_router)]
+impl AgentMcpServer {
+    // Use uniswap v3 tools to swap through v3 pools
+    #[tool(description = "Swaps ETH or tokens through Uniswap V3 pools for an exact input (min_amount_out) or an exact output (max_amount_in), picking the best fee tier of every hop unless `fees` is given and quoting omitted bounds on-chain",
+        output_schema = cached_schema_for_type::<SwapResult>())]
+    async fn swap_uniswap_v3(
+        &self,
+        Parameters(input): Parameters<super::uniswap_v3_tools::UniswapV3SwapInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .swap_v3(input)
+            .await
+            .map_err(|e| tool_error("token swap failed", e))?;
+        structured_result(reply)
+    }
+
+    // Quote a swap on-chain with the uniswap v3 quoter
+    #[tool(
+        description = "Quotes a swap on-chain with the Uniswap V3 QuoterV2, for an exact input or output amount, picking the best fee tier of every hop unless `fees` is given",
+        output_schema = cached_schema_for_type::<UniswapV3QuoteResult>()
+    )]
+    async fn get_uniswap_v3_quote(
+        &self,
+        Parameters(input): Parameters<super::uniswap_v3_tools::UniswapV3QuoteInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .quote_v3(input)
+            .await
+            .map_err(|e| tool_error("uniswap v3 quote failed", e))?;
+        structured_result(reply)
+    }
+}
+
+#[tool_router(router = token_list_tool_router)]
+impl AgentMcpServer {
+    // Search the token list of a chain
+    #[tool(description = "Searches a chain's token list by symbol or name, returning the token addresses, symbols and decimals. Token inputs of the other tools accept these symbols",
+        output_schema = cached_schema_for_type::<TokenSearchResult>())]
+    async fn search_tokens(
+        &self,
+        Parameters(input): Parameters<super::token_list_tools::TokenSearchInput>,
+    ) -> std::result::Result<CallToolResult, ErrorData> {
+        let reply = self
+            .ctx
+            .m_tool
+            .search_tokens(input)
+            .await
+            .map_err(|e| tool_error("token search failed", e))?;
+        structured_result(reply)
     }
 }
 
 #[tool_handler]
+#[prompt_handler]
 impl ServerHandler for AgentMcpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
@@ -251,7 +598,102 @@ impl ServerHandler for AgentMcpServer {
                 .enable_tools()
                 .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some("This Server provides the following tools: eth_tools | brave_tools | zero_x_tools | uniswap_tools".to_string()),
+            instructions: Some(format!(
+                "This Server provides the following tools: {}",
+                self.ctx.m_tool.enabled_groups_summary()
+            )),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ListResourcesResult, ErrorData> {
+        let resource = |uri: &str, name: &str, description: &str| {
+            let mut resource = RawResource::new(uri, name);
+            resource.description = Some(description.to_string());
+            resource.mime_type = Some(String::from("application/json"));
+            resource.no_annotation()
+        };
+        Ok(ListResourcesResult::with_all_items(vec![
+            resource(
+                resources::ACCOUNTS_URI,
+                "managed_accounts",
+                "Addresses and derivation paths of the accounts the server signs for",
+            ),
+            resource(
+                resources::CHAIN_HEAD_URI,
+                "chain_head",
+                "Latest block number, base fee and chain id of the default chain",
+            ),
+        ]))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ListResourceTemplatesResult, ErrorData> {
+        let template = |uri_template: &str, name: &str, description: &str| {
+            RawResourceTemplate {
+                uri_template: uri_template.to_string(),
+                name: name.to_string(),
+                title: None,
+                description: Some(description.to_string()),
+                mime_type: Some(String::from("application/json")),
+            }
+            .no_annotation()
+        };
+        Ok(ListResourceTemplatesResult::with_all_items(vec![
+            template(
+                resources::CHAIN_HEAD_TEMPLATE,
+                "chain_head",
+                "Latest block number, base fee and chain id of a chain selected by name or chain id",
+            ),
+            template(
+                resources::TOKEN_TEMPLATE,
+                "token_metadata",
+                "Name, symbol, decimals and total supply of an ERC20 token, by address or ENS name",
+            ),
+        ]))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ReadResourceResult, ErrorData> {
+        let m_tool = &self.ctx.m_tool;
+        let Some(resource) = ResourceUri::parse(&request.uri) else {
+            return Err(ErrorData::resource_not_found(
+                format!("unknown resource {}", request.uri),
+                Some(json!({ "uri": request.uri })),
+            ));
+        };
+
+        match resource {
+            ResourceUri::Accounts => {
+                let accounts = m_tool
+                    .managed_accounts()
+                    .await
+                    .map_err(|e| tool_error("failed to read accounts", e))?;
+                json_resource(request.uri, &accounts)
+            }
+            ResourceUri::ChainHead { chain } => {
+                let head = m_tool
+                    .chain_head(chain)
+                    .await
+                    .map_err(|e| tool_error("failed to read chain head", e))?;
+                json_resource(request.uri, &head)
+            }
+            ResourceUri::Token { address, chain } => {
+                let token = m_tool
+                    .token_metadata(address, chain)
+                    .await
+                    .map_err(|e| tool_error("failed to read token metadata", e))?;
+                json_resource(request.uri, &token)
+            }
         }
     }
 }
@@ -3,24 +3,66 @@
 //! Provides a wrapper for querying the Brave Search API via the `BraveTools` trait,
 //! including request building, authentication, and basic error handling.
 //!
-//! Note: This does not parse or deserialize the result for processing as the Agent will be
-//!         expected to interpret this.
-use anyhow::anyhow;
-use reqwest::Client;
-use rmcp::schemars;
-use std::collections::{HashMap, HashSet};
-use regex::Regex;
+//! Addresses are extracted from the web, news and discussion results of the search, each one
+//! annotated so the agent can tell a token contract from a wallet or a scam:
+//! - The results mentioning it, with their URL and the text around the address
+//! - Whether code is deployed at it on the selected chain
+//! - Its ERC20 symbol and decimals, when it answers those calls
+//!
+//! Addresses are ranked by the number of independent sites mentioning them. Hex strings longer
+//! than an address, such as transaction hashes, are not taken for addresses.
+//!
+//! The general search returns the results themselves instead (title, URL, description and age),
+//! for news, documentation or protocol research, with the news and discussion sections on request.
+//! It can page through results and filter them by freshness, country and safe search level.
+use crate::common::chains::Chain;
+use crate::common::context::MAX_BRAVE_RESULT_COUNT;
+use crate::common::error::ToolError;
 use crate::tools::MultiTool;
+use crate::tools::eth_tools::ERC20;
 use crate::tools::traits::BraveTools;
+use ethers::prelude::Address;
+use ethers::utils::to_checksum;
+use futures::stream::{self, StreamExt};
+use regex::Regex;
+use reqwest::{Client, Url};
+use rmcp::schemars;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::sync::LazyLock;
 
 const BASE_URL: &str = "https://api.search.brave.com/res/v1";
 const QUERY_PARAM: &str = "q";
 const QUERY_PARAM_COUNT: &str = "count";
+const QUERY_PARAM_OFFSET: &str = "offset";
+const QUERY_PARAM_FRESHNESS: &str = "freshness";
+const QUERY_PARAM_COUNTRY: &str = "country";
+const QUERY_PARAM_SAFESEARCH: &str = "safesearch";
+const QUERY_PARAM_RESULT_FILTER: &str = "result_filter";
+
+/// Brave Search API pages through at most 10 pages of results
+const MAX_OFFSET: u32 = 9;
 
 const HEADER_ACCEPT: &str = "Accept";
 const HEADER_ACCEPT_ENCODING: &str = "Accept-Encoding";
 const HEADER_SUBSCRIPTION_TOKEN: &str = "X-Subscription-Token";
 
+/// Characters of context kept on each side of an address in its snippets
+const SNIPPET_CONTEXT: usize = 60;
+/// Results listed per address, the others only count as mentions
+const MAX_SOURCES: usize = 3;
+/// Addresses annotated at the same time, each takes a few RPC calls
+const MAX_CONCURRENT_ANNOTATIONS: usize = 8;
+
+/// HTML tags of the result texts
+static TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>]*>").expect("Invalid regex pattern"));
+/// Addresses, word boundaries exclude longer hex strings such as transaction hashes
+static ADDRESS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b0x[a-fA-F0-9]{40}\b").expect("Invalid regex pattern"));
+
 /// Input payload for a Brave web search request.
 ///
 /// Designed for use with API schemas and deserialization.
@@ -28,6 +70,287 @@ const HEADER_SUBSCRIPTION_TOKEN: &str = "X-Subscription-Token";
 pub struct WebSearchInput {
     #[schemars(description = "Query string to use for web search")]
     pub query: String,
+    #[schemars(
+        description = "Chain name or chain id to check the found addresses on, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Contract addresses found in the results of a web search.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct AddressSearchResult {
+    #[schemars(description = "Query string used for the search")]
+    pub query: String,
+    #[schemars(description = "Name of the chain the addresses were checked on")]
+    pub chain: String,
+    #[schemars(
+        description = "Unique addresses found in the search results, most mentioned first, then in order of appearance"
+    )]
+    pub addresses: Vec<FoundAddress>,
+}
+
+/// An address found in the search results, with where it was found and what it is on-chain.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct FoundAddress {
+    #[schemars(description = "Checksummed address")]
+    pub address: String,
+    #[schemars(description = "Number of independent sites whose results mention the address")]
+    pub mentions: usize,
+    #[schemars(description = "First results mentioning the address")]
+    pub sources: Vec<AddressSource>,
+    #[schemars(
+        description = "Whether code is deployed at the address on the chain, absent if the lookup failed"
+    )]
+    pub deployed: Option<bool>,
+    #[schemars(description = "ERC20 metadata, if the contract answers `symbol` and `decimals`")]
+    pub token: Option<FoundToken>,
+}
+
+/// A search result mentioning an address.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct AddressSource {
+    pub url: String,
+    pub title: String,
+    #[schemars(description = "Text around the address in the result")]
+    pub snippet: String,
+}
+
+/// ERC20 metadata of a found address.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct FoundToken {
+    pub name: Option<String>,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl Display for AddressSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.addresses.is_empty() {
+            return write!(f, "No addresses found for {}", self.query);
+        }
+        write!(
+            f,
+            "{} addresses found for {}, checked on {}:",
+            self.addresses.len(),
+            self.query,
+            self.chain
+        )?;
+        for found in &self.addresses {
+            write!(f, "\n- {found}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for FoundAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.address)?;
+        match (&self.token, self.deployed) {
+            (Some(token), _) => {
+                write!(f, " (token {}, {} decimals)", token.symbol, token.decimals)?
+            }
+            (None, Some(true)) => f.write_str(" (contract)")?,
+            (None, Some(false)) => f.write_str(" (no code)")?,
+            (None, None) => {}
+        }
+        write!(f, ", mentioned by {} sites", self.mentions)?;
+        if let Some(source) = self.sources.first() {
+            write!(f, ", e.g. {}: \"{}\"", source.url, source.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+/// How recent the results of a general search must be.
+#[derive(Debug, Clone, Copy, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Freshness {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Freshness {
+    /// Value of the `freshness` query parameter
+    fn param(self) -> &'static str {
+        match self {
+            Freshness::Day => "pd",
+            Freshness::Week => "pw",
+            Freshness::Month => "pm",
+            Freshness::Year => "py",
+        }
+    }
+}
+
+/// Filtering of adult content in the results of a general search.
+#[derive(Debug, Clone, Copy, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SafeSearch {
+    Off,
+    Moderate,
+    Strict,
+}
+
+impl SafeSearch {
+    /// Value of the `safesearch` query parameter
+    fn param(self) -> &'static str {
+        match self {
+            SafeSearch::Off => "off",
+            SafeSearch::Moderate => "moderate",
+            SafeSearch::Strict => "strict",
+        }
+    }
+}
+
+/// General web search input struct
+///
+///     Fields:
+///         query (String): Query string to use for web search
+///         count (Option<u32>): Number of web results, defaults to the configured result count
+///         offset (Option<u32>): Page of results to return, 0 for the first one
+///         freshness (Option<Freshness>): How recent the results must be
+///         country (Option<String>): Two letter country code the results come from
+///         safesearch (Option<SafeSearch>): Filtering of adult content, Brave's default when omitted
+///         include_news (Option<bool>): Whether to return the news section, defaults to false
+///         include_discussions (Option<bool>): Whether to return the discussions section, defaults to false
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct GeneralSearchInput {
+    #[schemars(description = "Query string to use for web search")]
+    pub query: String,
+    #[schemars(
+        description = "Number of web results to return, at most 20, defaults to the server's result count"
+    )]
+    pub count: Option<u32>,
+    #[schemars(
+        description = "Page of results to return, in pages of `count` results: 0 for the first page, at most 9"
+    )]
+    pub offset: Option<u32>,
+    #[schemars(
+        description = "Only return results discovered within the last day, week, month or year"
+    )]
+    pub freshness: Option<Freshness>,
+    #[schemars(description = "Two letter country code the results should come from, e.g. US")]
+    pub country: Option<String>,
+    #[schemars(description = "Filtering of adult content: off, moderate or strict")]
+    pub safesearch: Option<SafeSearch>,
+    #[schemars(description = "Whether to also return news results, defaults to false")]
+    pub include_news: Option<bool>,
+    #[schemars(description = "Whether to also return forum discussions, defaults to false")]
+    pub include_discussions: Option<bool>,
+}
+
+/// Results of a general web search.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GeneralSearchResult {
+    #[schemars(description = "Query string used for the search")]
+    pub query: String,
+    #[schemars(description = "Web results, in ranking order")]
+    pub web: Vec<WebResult>,
+    #[schemars(description = "News results, when requested")]
+    pub news: Vec<WebResult>,
+    #[schemars(description = "Forum discussions, when requested")]
+    pub discussions: Vec<WebResult>,
+    #[schemars(description = "Whether the next `offset` has more results")]
+    pub more_results_available: bool,
+}
+
+/// A result of a general web search, its texts stripped of HTML highlighting.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct WebResult {
+    pub title: String,
+    pub url: String,
+    pub description: String,
+    #[schemars(description = "Age of the page as reported by Brave, e.g. \"2 days ago\"")]
+    pub age: Option<String>,
+}
+
+impl Display for GeneralSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} web results for {}", self.web.len(), self.query)?;
+        for (section, results) in [
+            ("Web", &self.web),
+            ("News", &self.news),
+            ("Discussions", &self.discussions),
+        ] {
+            if results.is_empty() {
+                continue;
+            }
+            write!(f, "\n{section}:")?;
+            for result in results {
+                write!(f, "\n- {result}")?;
+            }
+        }
+        if self.more_results_available {
+            f.write_str("\nMore results are available with the next offset")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for WebResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.title, self.url)?;
+        if let Some(age) = &self.age {
+            write!(f, " [{age}]")?;
+        }
+        if !self.description.is_empty() {
+            write!(f, ": {}", self.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// Web search response, only the result sections are decoded.
+#[derive(Debug, Default, serde::Deserialize)]
+struct SearchResponse {
+    query: Option<QueryInfo>,
+    web: Option<ResultSection>,
+    news: Option<ResultSection>,
+    discussions: Option<ResultSection>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct QueryInfo {
+    #[serde(default)]
+    more_results_available: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResultSection {
+    #[serde(default)]
+    results: Vec<SearchHit>,
+}
+
+/// A search result, its texts may hold HTML highlighting tags
+#[derive(Debug, serde::Deserialize)]
+struct SearchHit {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    extra_snippets: Vec<String>,
+    age: Option<String>,
+}
+
+impl SearchResponse {
+    fn hits(&self) -> impl Iterator<Item = &SearchHit> {
+        [&self.web, &self.news, &self.discussions]
+            .into_iter()
+            .flatten()
+            .flat_map(|section| &section.results)
+    }
+}
+
+/// An address mentioned in the search results, before its on-chain lookup
+#[derive(Debug)]
+struct Mention {
+    address: Address,
+    sites: HashSet<String>,
+    sources: Vec<AddressSource>,
 }
 
 /// Holds configuration and HTTP client for Brave Search requests.
@@ -35,6 +358,7 @@ pub struct BraveContext {
     client: Client,
     api_key: String,
     base_url: String,
+    result_count: u32,
 }
 
 impl BraveContext {
@@ -42,73 +366,388 @@ impl BraveContext {
     ///
     /// # Arguments
     /// * `api_key` - Brave Search API subscription token.
-    pub fn new(api_key: String) -> Self {
+    /// * `result_count` - Number of search results requested per query.
+    pub fn new(api_key: String, result_count: u32) -> Self {
         Self {
             client: Client::new(),
             api_key,
             base_url: String::from(BASE_URL),
+            result_count,
+        }
+    }
+
+    /// Sends a web search request with the given query parameters and decodes its results
+    async fn web_search(
+        &self,
+        params: &HashMap<&str, String>,
+    ) -> Result<SearchResponse, ToolError> {
+        let url = format!("{}/web/search", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header(HEADER_ACCEPT, "application/json")
+            .header(HEADER_ACCEPT_ENCODING, "gzip")
+            .header(HEADER_SUBSCRIPTION_TOKEN, &self.api_key)
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| upstream_error(None, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(upstream_error(Some(status.as_u16()), error));
         }
+
+        response
+            .json()
+            .await
+            .map_err(|e| upstream_error(Some(status.as_u16()), e))
     }
 }
 
 impl BraveTools for MultiTool {
     /// Performs a Brave web search using the given query string.
     ///
-    /// Sends a GET request to the Brave Search API and returns the raw JSON response.
+    /// Sends a GET request to the Brave Search API and returns the addresses found in the response.
     ///
     /// # Arguments
     /// * `query` - Search term to query Brave Search.
     ///
     /// # Errors
     /// Returns an error if the HTTP request fails or if the API responds with a non-success status.
-    async fn search(&self, query: String) -> anyhow::Result<String> {
-        let url = format!("{}/web/search", self.brave_ctx.base_url);
+    async fn search(
+        &self,
+        query: String,
+        chain: Option<String>,
+    ) -> anyhow::Result<AddressSearchResult> {
+        let brave_ctx = self.brave()?;
+        let chain = self.chain(chain.as_deref())?;
 
         // Set Query parameters, can add more if needed
         let mut params = HashMap::new();
         params.insert(QUERY_PARAM, query.clone());
-        params.insert(QUERY_PARAM_COUNT, "3".to_string());
+        params.insert(QUERY_PARAM_COUNT, brave_ctx.result_count.to_string());
 
-        let response = self
-            .brave_ctx
-            .client
-            .get(&url)
-            .header(HEADER_ACCEPT, "application/json")
-            .header(HEADER_ACCEPT_ENCODING, "gzip")
-            .header(HEADER_SUBSCRIPTION_TOKEN, &self.brave_ctx.api_key)
-            .query(&params)
-            .send()
-            .await?;
+        let response = brave_ctx.web_search(&params).await?;
+
+        // Annotate concurrently rather than one RPC round trip after another, keeping the order
+        let addresses = stream::iter(extract_addresses(&response))
+            .map(|mention| self.annotate_address(chain, mention))
+            .buffered(MAX_CONCURRENT_ANNOTATIONS)
+            .collect()
+            .await;
+
+        Ok(AddressSearchResult {
+            query,
+            chain: chain.config.name.clone(),
+            addresses,
+        })
+    }
+
+    /// Performs a general Brave web search, returning the results themselves.
+    ///
+    /// # Errors
+    /// Returns an error if a parameter is out of range, if the HTTP request fails or if the API
+    /// responds with a non-success status.
+    async fn general_search(
+        &self,
+        input: GeneralSearchInput,
+    ) -> anyhow::Result<GeneralSearchResult> {
+        let brave_ctx = self.brave()?;
+
+        let count = input.count.unwrap_or(brave_ctx.result_count);
+        if !(1..=MAX_BRAVE_RESULT_COUNT).contains(&count) {
+            return Err(ToolError::invalid_params(
+                "count",
+                format!("must be between 1 and {MAX_BRAVE_RESULT_COUNT}"),
+            )
+            .into());
+        }
+        let offset = input.offset.unwrap_or(0);
+        if offset > MAX_OFFSET {
+            return Err(ToolError::invalid_params(
+                "offset",
+                format!("must be at most {MAX_OFFSET}"),
+            )
+            .into());
+        }
+
+        let mut params = HashMap::new();
+        params.insert(QUERY_PARAM, input.query.clone());
+        params.insert(QUERY_PARAM_COUNT, count.to_string());
+        params.insert(QUERY_PARAM_OFFSET, offset.to_string());
+        if let Some(freshness) = input.freshness {
+            params.insert(QUERY_PARAM_FRESHNESS, freshness.param().to_string());
+        }
+        if let Some(country) = &input.country {
+            let country = country.trim();
+            if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ToolError::invalid_params(
+                    "country",
+                    format!("{country} is not a two letter country code"),
+                )
+                .into());
+            }
+            params.insert(QUERY_PARAM_COUNTRY, country.to_ascii_uppercase());
+        }
+        if let Some(safesearch) = input.safesearch {
+            params.insert(QUERY_PARAM_SAFESEARCH, safesearch.param().to_string());
+        }
+
+        // Only the requested sections are returned by Brave
+        let mut sections = vec!["web"];
+        if input.include_news.unwrap_or(false) {
+            sections.push("news");
+        }
+        if input.include_discussions.unwrap_or(false) {
+            sections.push("discussions");
+        }
+        params.insert(QUERY_PARAM_RESULT_FILTER, sections.join(","));
+
+        let response = brave_ctx.web_search(&params).await?;
+        let results = |section: &Option<ResultSection>| -> Vec<WebResult> {
+            section
+                .iter()
+                .flat_map(|section| &section.results)
+                .map(WebResult::from)
+                .collect()
+        };
 
-        if !response.status().is_success() {
-            return Err(anyhow!(
-                "Brave Search API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            ));
+        Ok(GeneralSearchResult {
+            web: results(&response.web),
+            news: results(&response.news),
+            discussions: results(&response.discussions),
+            more_results_available: response
+                .query
+                .as_ref()
+                .is_some_and(|query| query.more_results_available),
+            query: input.query,
+        })
+    }
+}
+
+impl From<&SearchHit> for WebResult {
+    fn from(hit: &SearchHit) -> Self {
+        Self {
+            title: strip_tags(&hit.title),
+            url: hit.url.clone(),
+            description: strip_tags(&hit.description),
+            age: hit.age.clone(),
         }
+    }
+}
 
-        let formatted_results = response.text().await?;
-        let addresses = parse_addresses(&formatted_results);
+impl MultiTool {
+    /// Looks up what a found address is on the chain. Lookups failing only leave the fields
+    /// empty, a search never fails because of an address.
+    async fn annotate_address(&self, chain: &Chain, mention: Mention) -> FoundAddress {
+        let deployed = self
+            .code_size(chain, mention.address)
+            .await
+            .map(|size| size > 0)
+            .ok();
 
-        Ok(addresses.join(", "))
+        // Only contracts can be tokens, any contract failing the calls isn't one
+        let token = match deployed {
+            Some(true) => {
+                let contract = ERC20::new(mention.address, chain.provider.clone());
+                let (symbol_call, decimals_call, name_call) =
+                    (contract.symbol(), contract.decimals(), contract.name());
+                let (symbol, decimals, name) =
+                    tokio::join!(symbol_call.call(), decimals_call.call(), name_call.call());
+                match (symbol, decimals) {
+                    (Ok(symbol), Ok(decimals)) => Some(FoundToken {
+                        name: name.ok(),
+                        symbol,
+                        decimals,
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        FoundAddress {
+            address: to_checksum(&mention.address, None),
+            mentions: mention.sites.len(),
+            sources: mention.sources,
+            deployed,
+            token,
+        }
+    }
+}
+
+/// Error for a failed request to the Brave Search API
+fn upstream_error(status: Option<u16>, message: impl std::fmt::Display) -> ToolError {
+    ToolError::Upstream {
+        service: String::from("Brave Search"),
+        status,
+        message: message.to_string(),
     }
 }
 
-fn parse_addresses(brave_result: &str) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut unique_addresses = Vec::new();
-    let regex = Regex::new(r"0x[a-fA-F0-9]{40}").expect("Invalid regex pattern");
+/// Removes the HTML tags Brave highlights the query with, e.g. `<strong>`
+fn strip_tags(text: &str) -> String {
+    TAG_REGEX.replace_all(text, "").into_owned()
+}
+
+/// Extracts the addresses mentioned by the search results, most mentioned first.
+///
+/// An address is counted once per site (the host of the result URL), so that several pages of
+/// the same site don't outrank independent sources.
+fn extract_addresses(response: &SearchResponse) -> Vec<Mention> {
+    let mut mentions: Vec<Mention> = Vec::new();
+
+    for hit in response.hits() {
+        let site = Url::parse(&hit.url)
+            .ok()
+            .and_then(|url| {
+                url.host_str()
+                    .map(|host| host.trim_start_matches("www.").to_string())
+            })
+            .unwrap_or_else(|| hit.url.clone());
+        let title = strip_tags(&hit.title);
+        let texts = [&hit.title, &hit.description, &hit.url]
+            .into_iter()
+            .chain(&hit.extra_snippets);
 
-    for cap in regex.find_iter(brave_result) {
-        let address = cap.as_str().to_string();
-        let address_lower = address.to_lowercase();
+        let mut found_in_hit = HashSet::new();
+        for text in texts {
+            let text = strip_tags(text);
+            for found in ADDRESS_REGEX.find_iter(&text) {
+                let Ok(address) = found.as_str().parse::<Address>() else {
+                    continue;
+                };
+                if !found_in_hit.insert(address) {
+                    continue;
+                }
 
-        if !seen.contains(&address_lower) {
-            seen.insert(address_lower);
-            unique_addresses.push(address);
+                let index = match mentions.iter().position(|m| m.address == address) {
+                    Some(index) => index,
+                    None => {
+                        mentions.push(Mention {
+                            address,
+                            sites: HashSet::new(),
+                            sources: Vec::new(),
+                        });
+                        mentions.len() - 1
+                    }
+                };
+                let mention = &mut mentions[index];
+                mention.sites.insert(site.clone());
+                if mention.sources.len() < MAX_SOURCES {
+                    mention.sources.push(AddressSource {
+                        url: hit.url.clone(),
+                        title: title.clone(),
+                        snippet: snippet(&text, found.range()),
+                    });
+                }
+            }
         }
     }
 
-    unique_addresses
-}
\ No newline at end of file
+    // Stable sort, addresses mentioned as often keep their order of appearance
+    mentions.sort_by_key(|mention| std::cmp::Reverse(mention.sites.len()));
+    mentions
+}
+
+/// Text around a match, cut on char boundaries and marked with ellipses where it was cut
+fn snippet(text: &str, range: Range<usize>) -> String {
+    let start = text[..range.start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT - 1)
+        .map_or(0, |(index, _)| index);
+    let end = text[range.end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT)
+        .map_or(text.len(), |(index, _)| range.end + index);
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(text[start..end].trim());
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_addresses() {
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let wallet = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let tx_hash = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+        let response: SearchResponse = serde_json::from_value(serde_json::json!({
+            "web": { "results": [
+                {
+                    "title": "USDC token",
+                    "url": "https://etherscan.io/token/0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                    "description": format!("Contract <strong>{usdc}</strong>, sent by {wallet} in {tx_hash}")
+                },
+                {
+                    "title": "USDC on Etherscan",
+                    "url": "https://www.etherscan.io/address/x",
+                    "description": format!("The USDC contract is {usdc}")
+                }
+            ]},
+            "news": { "results": [
+                { "title": "Circle", "url": "https://news.example.org/usdc", "description": format!("USDC ({usdc})") },
+                { "title": "Wallet", "url": "https://blog.example.org/a", "description": format!("{wallet} {wallet}") }
+            ]}
+        }))
+        .unwrap();
+
+        let mentions = extract_addresses(&response);
+        let addresses: Vec<String> = mentions
+            .iter()
+            .map(|m| to_checksum(&m.address, None))
+            .collect();
+        assert_eq!(addresses, [usdc, wallet]);
+
+        // etherscan.io and www.etherscan.io are the same site
+        assert_eq!(mentions[0].sites.len(), 2);
+        assert_eq!(mentions[0].sources.len(), 3);
+        assert_eq!(
+            mentions[0].sources[0].snippet,
+            format!("Contract {usdc}, sent by {wallet} in {}…", &tx_hash[..4])
+        );
+        assert_eq!(mentions[1].sites.len(), 2);
+    }
+
+    #[test]
+    fn test_web_results() {
+        let response: SearchResponse = serde_json::from_value(serde_json::json!({
+            "query": { "original": "uniswap v4", "more_results_available": true },
+            "web": { "results": [{
+                "title": "<strong>Uniswap</strong> v4",
+                "url": "https://docs.uniswap.org/contracts/v4/overview",
+                "description": "Hooks in <strong>Uniswap v4</strong>",
+                "age": "2 days ago"
+            }]}
+        }))
+        .unwrap();
+
+        assert!(response.query.unwrap().more_results_available);
+        assert!(response.news.is_none());
+        let result = WebResult::from(&response.web.unwrap().results[0]);
+        assert_eq!(result.title, "Uniswap v4");
+        assert_eq!(result.description, "Hooks in Uniswap v4");
+        assert_eq!(result.age.as_deref(), Some("2 days ago"));
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = format!("{}0x{}", "a".repeat(100), "b".repeat(100));
+        let snippet = snippet(&text, 100..102);
+
+        assert_eq!(snippet, format!("…{}0x{}…", "a".repeat(60), "b".repeat(60)));
+        assert_eq!(super::snippet("é 0x é", 3..5), "é 0x é");
+    }
+}
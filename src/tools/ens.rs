@@ -0,0 +1,110 @@
+//! ENS name resolution shared by every tool taking or returning addresses.
+//!
+//! Address inputs accept either a hex address or an ENS name such as `vitalik.eth`. Names are
+//! resolved with the configured providers, addresses in tool results are reverse resolved so the
+//! agent sees the primary name next to the address when one is set.
+//!
+//! ENS is deployed on Ethereum mainnet, so names are resolved against the configured mainnet chain
+//! when there is one and against the selected chain otherwise (e.g. an Anvil fork of mainnet).
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
+use crate::tools::MultiTool;
+use crate::tools::results::AddressInfo;
+use anyhow::Result;
+use ethers::prelude::*;
+
+/// Chain id of Ethereum mainnet, where the ENS registry lives
+const ENS_CHAIN_ID: u64 = 1;
+
+/// An address input before resolution, either a hex address or an ENS name.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AddressInput {
+    Address(Address),
+    Name(String),
+}
+
+impl AddressInput {
+    /// Parses a tool input, anything that isn't a hex address must look like an ENS name
+    pub(crate) fn parse(input: &str) -> Result<Self, ToolError> {
+        let input = input.trim();
+        if input.starts_with("0x") || input.starts_with("0X") {
+            return input
+                .parse::<Address>()
+                .map(AddressInput::Address)
+                .map_err(|e| ToolError::invalid_params("address", format!("{input}: {e}")));
+        }
+
+        let is_name = input.contains('.')
+            && input
+                .split('.')
+                .all(|label| !label.is_empty() && !label.contains(char::is_whitespace));
+        if !is_name {
+            return Err(ToolError::invalid_params(
+                "address",
+                format!("{input} is neither an address nor an ENS name"),
+            ));
+        }
+        Ok(AddressInput::Name(input.to_lowercase()))
+    }
+}
+
+impl MultiTool {
+    /// Provider used for ENS lookups, the mainnet chain when configured
+    fn ens_provider<'a>(&'a self, chain: &'a Chain) -> &'a Provider<Http> {
+        self.chains
+            .iter()
+            .find(|chain| chain.config.chain_id == ENS_CHAIN_ID)
+            .unwrap_or(chain)
+            .provider
+            .as_ref()
+    }
+
+    /// Resolves an address input, looking up ENS names
+    pub(crate) async fn resolve_address(&self, chain: &Chain, input: &str) -> Result<Address> {
+        let name = match AddressInput::parse(input)? {
+            AddressInput::Address(address) => return Ok(address),
+            AddressInput::Name(name) => name,
+        };
+
+        let address = match self.ens_provider(chain).resolve_name(&name).await {
+            Ok(address) => address,
+            // Names without a resolver are reported by ethers as ENS errors
+            Err(ProviderError::EnsError(_)) => Address::zero(),
+            Err(e) => return Err(ToolError::from_rpc(e)),
+        };
+        if address.is_zero() {
+            return Err(ToolError::not_found("ENS name", name).into());
+        }
+
+        tracing::info!("Resolved ENS name {name} to {address:?}");
+        Ok(address)
+    }
+
+    /// Describes an address for tool results, with its primary ENS name when one is set
+    pub(crate) async fn address_info(&self, chain: &Chain, address: Address) -> AddressInfo {
+        // Reverse records are optional, any lookup failure just leaves the bare address
+        let ens_name = self.ens_provider(chain).lookup_address(address).await.ok();
+        AddressInfo::new(address, ens_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address_input() {
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        assert_eq!(
+            AddressInput::parse(address).unwrap(),
+            AddressInput::Address(address.parse().unwrap())
+        );
+        assert_eq!(
+            AddressInput::parse(" Vitalik.eth ").unwrap(),
+            AddressInput::Name(String::from("vitalik.eth"))
+        );
+        assert!(AddressInput::parse("0x1234").is_err());
+        assert!(AddressInput::parse("vitalik").is_err());
+        assert!(AddressInput::parse("vitalik..eth").is_err());
+    }
+}
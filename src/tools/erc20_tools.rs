@@ -0,0 +1,438 @@
+//! ERC20 token transfers, approvals and allowances.
+//!
+//! Write operations are signed by the managed account given as `owner`, accounts the server holds
+//! no key for are rejected instead of falling back to another account. Transactions from the same
+//! account are serialized like every other transaction.
+//!
+//! # Key Operations
+//!
+//! - [`transfer`]: Transfer tokens from a managed account
+//! - [`approve`]: Allow a spender to move an exact amount of tokens, unlimited approvals must be
+//!   requested explicitly
+//! - [`allowance`]: Read how many tokens a spender may still move
+//! - [`revoke`]: Set a spender's allowance back to zero
+//!
+//! Amounts take an explicit unit and default to the token's base units, see
+//! [`crate::tools::units`].
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
+use crate::tools::eth_tools::ERC20;
+use crate::tools::results::{AddressInfo, Amount, TxResult, TxStatus};
+use crate::tools::traits::Erc20Tools;
+use crate::tools::units::AmountUnit;
+use crate::tools::{MultiTool, SignerClient};
+use anyhow::{Result, anyhow};
+use ethers::prelude::*;
+use rmcp::schemars;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+/// Token transfer input struct
+///
+///     Fields:
+///         token (String): The ERC20 token contract
+///         owner (String): The managed account sending the tokens
+///         to (String): The recipient of the tokens
+///         amount (String): The amount of tokens to transfer
+///         amount_unit (Option<AmountUnit>): Unit of the amount, defaults to base units
+///         chain (Option<String>): The chain to transfer on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct TokenTransferInput {
+    #[schemars(description = "The ERC20 token contract address, ENS name or token list symbol")]
+    pub token: String,
+    #[schemars(description = "The managed account address or ENS name sending the tokens")]
+    pub owner: String,
+    #[schemars(description = "The address or ENS name receiving the tokens")]
+    pub to: String,
+    #[schemars(description = "The amount of tokens to transfer, in `amount_unit`")]
+    pub amount: String,
+    #[schemars(description = "Unit of `amount`: wei (token base units) or token, defaults to wei")]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Token approval input struct
+///
+///     Fields:
+///         token (String): The ERC20 token contract
+///         owner (String): The managed account granting the allowance
+///         spender (String): The account or contract allowed to spend the tokens
+///         amount (Option<String>): The exact amount the spender may move
+///         amount_unit (Option<AmountUnit>): Unit of the amount, defaults to base units
+///         unlimited (Option<bool>): Approve the maximum amount instead of `amount`
+///         chain (Option<String>): The chain to approve on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ApproveInput {
+    #[schemars(description = "The ERC20 token contract address, ENS name or token list symbol")]
+    pub token: String,
+    #[schemars(description = "The managed account address or ENS name granting the allowance")]
+    pub owner: String,
+    #[schemars(description = "The address or ENS name allowed to spend the tokens")]
+    pub spender: String,
+    #[schemars(
+        description = "The exact amount the spender may move, in `amount_unit`, required unless `unlimited` is set"
+    )]
+    pub amount: Option<String>,
+    #[schemars(description = "Unit of `amount`: wei (token base units) or token, defaults to wei")]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "Approve the maximum amount instead of `amount`, only use it when explicitly asked"
+    )]
+    pub unlimited: Option<bool>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Allowance input struct, also used to revoke an allowance
+///
+///     Fields:
+///         token (String): The ERC20 token contract
+///         owner (String): The account granting the allowance
+///         spender (String): The account or contract allowed to spend the tokens
+///         chain (Option<String>): The chain to look on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AllowanceInput {
+    #[schemars(description = "The ERC20 token contract address, ENS name or token list symbol")]
+    pub token: String,
+    #[schemars(description = "The address or ENS name granting the allowance")]
+    pub owner: String,
+    #[schemars(description = "The address or ENS name allowed to spend the tokens")]
+    pub spender: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// A mined token transfer or approval.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct TokenTxResult {
+    #[serde(flatten)]
+    pub tx: TxResult,
+    #[schemars(description = "ERC20 token contract")]
+    pub token: AddressInfo,
+    #[schemars(description = "Recipient of a transfer, or spender of an approval")]
+    pub counterparty: AddressInfo,
+    #[schemars(description = "Amount transferred, or allowance granted")]
+    pub amount: Amount,
+}
+
+impl Display for TokenTxResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, Token: {}, Counterparty: {}, Amount: {}",
+            self.tx, self.token, self.counterparty, self.amount
+        )
+    }
+}
+
+/// Allowance an owner granted to a spender.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct AllowanceResult {
+    #[schemars(description = "Name of the chain the allowance was read on")]
+    pub chain: String,
+    #[schemars(description = "ERC20 token contract")]
+    pub token: AddressInfo,
+    #[schemars(description = "Account granting the allowance")]
+    pub owner: AddressInfo,
+    #[schemars(description = "Account or contract allowed to spend the tokens")]
+    pub spender: AddressInfo,
+    #[schemars(description = "Amount the spender may still move")]
+    pub allowance: Amount,
+    #[schemars(description = "Whether the allowance is the maximum amount, i.e. unlimited")]
+    pub unlimited: bool,
+}
+
+impl Display for AllowanceResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Allowance of {} from {} on {}: ",
+            self.spender, self.owner, self.chain
+        )?;
+        if self.unlimited {
+            write!(f, "unlimited {}", self.allowance.symbol)
+        } else {
+            write!(f, "{}", self.allowance)
+        }
+    }
+}
+
+/// Approvals sent by `ensure_allowance` to raise an allowance
+#[derive(Debug, Default)]
+pub(crate) struct AllowanceUpdate {
+    /// Approval of 0 sent first when the allowance wasn't zero
+    pub(crate) reset: Option<TxResult>,
+    /// Approval of the amount
+    pub(crate) approval: Option<TxResult>,
+}
+
+impl MultiTool {
+    /// Sends a state changing ERC20 call signed by the managed owner account, callers hold the
+    /// owner's signer lock
+    async fn send_token_call(
+        &self,
+        chain: &Chain,
+        owner: Address,
+        token: Address,
+        call: impl FnOnce(ERC20<SignerClient>) -> ContractCall<SignerClient, bool>,
+    ) -> Result<TransactionReceipt> {
+        let wallet = self.managed_wallet(owner)?;
+        let contract = ERC20::new(token, Arc::new(self.signer(chain, wallet)));
+        let call = call(contract);
+        let pending_tx = call.send().await.map_err(ToolError::from_contract)?;
+        pending_tx
+            .await
+            .map_err(ToolError::from_rpc)?
+            .ok_or_else(|| anyhow!("receipt was empty"))
+    }
+
+    /// Sets the allowance of a spender, shared by approvals and revocations
+    async fn set_allowance(
+        &self,
+        chain: &Chain,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: Amount,
+    ) -> Result<TokenTxResult> {
+        let value = amount.value();
+
+        // Serialize transactions from this account so concurrent calls don't race on the nonce
+        let guard = self.signer_locks.lock(owner).await;
+        let receipt = self
+            .send_token_call(chain, owner, token, |contract| {
+                contract.approve(spender, value)
+            })
+            .await?;
+        drop(guard);
+
+        Ok(TokenTxResult {
+            tx: TxResult::new(
+                chain,
+                &receipt,
+                self.address_info(chain, owner).await,
+                Some(self.address_info(chain, token).await),
+            ),
+            token: self.address_info(chain, token).await,
+            counterparty: self.address_info(chain, spender).await,
+            amount,
+        })
+    }
+
+    /// Makes sure a spender may move `amount` tokens of the owner, approving exactly that amount
+    /// when the current allowance falls short. Returns the mined approvals, if any were needed.
+    ///
+    /// A non-zero allowance is reset to 0 first, as tokens such as USDT reject changing an
+    /// allowance from one non-zero value to another.
+    ///
+    /// Callers hold the owner's signer lock, so the allowance can't be used by another
+    /// transaction of the same account before they spend it.
+    pub(crate) async fn ensure_allowance(
+        &self,
+        chain: &Chain,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<AllowanceUpdate> {
+        let allowance = ERC20::new(token, chain.provider.clone())
+            .allowance(owner, spender)
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        if allowance >= amount {
+            return Ok(AllowanceUpdate::default());
+        }
+
+        tracing::info!("Allowance of {spender:?} is {allowance}, approving {amount}");
+        let mut update = AllowanceUpdate::default();
+        if !allowance.is_zero() {
+            update.reset = Some(
+                self.send_approval(chain, token, owner, spender, U256::zero())
+                    .await?,
+            );
+        }
+        update.approval = Some(
+            self.send_approval(chain, token, owner, spender, amount)
+                .await?,
+        );
+        Ok(update)
+    }
+
+    /// Sends an approval of `amount` for `ensure_allowance`, failing when it reverts
+    async fn send_approval(
+        &self,
+        chain: &Chain,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<TxResult> {
+        let receipt = self
+            .send_token_call(chain, owner, token, |contract| {
+                contract.approve(spender, amount)
+            })
+            .await?;
+        let approval = TxResult::new(
+            chain,
+            &receipt,
+            self.address_info(chain, owner).await,
+            Some(self.address_info(chain, token).await),
+        );
+        if approval.status == TxStatus::Reverted {
+            return Err(ToolError::Reverted {
+                reason: Some(format!("approval {} reverted", approval.hash)),
+                data: None,
+            }
+            .into());
+        }
+        Ok(approval)
+    }
+}
+
+impl Erc20Tools for MultiTool {
+    /// transfer
+    ///
+    ///     Description:
+    ///         Transfers tokens from a managed account and returns the mined transaction.
+    ///
+    async fn transfer(&self, input: TokenTransferInput) -> Result<TokenTxResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let token = self.resolve_token_address(chain, &input.token).await?;
+        let owner = self.resolve_address(chain, &input.owner).await?;
+        let to = self.resolve_address(chain, &input.to).await?;
+        let amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(AmountUnit::Wei),
+                Some(token),
+            )
+            .await?;
+
+        let value = amount.value();
+
+        // Serialize transactions from this account so concurrent calls don't race on the nonce
+        let guard = self.signer_locks.lock(owner).await;
+        let receipt = self
+            .send_token_call(chain, owner, token, |contract| contract.transfer(to, value))
+            .await?;
+        drop(guard);
+
+        Ok(TokenTxResult {
+            tx: TxResult::new(
+                chain,
+                &receipt,
+                self.address_info(chain, owner).await,
+                Some(self.address_info(chain, token).await),
+            ),
+            token: self.address_info(chain, token).await,
+            counterparty: self.address_info(chain, to).await,
+            amount,
+        })
+    }
+
+    /// approve
+    ///
+    ///     Description:
+    ///         Allows a spender to move an exact amount of tokens from a managed account. The
+    ///         maximum amount is only approved when `unlimited` is set.
+    ///
+    async fn approve(&self, input: ApproveInput) -> Result<TokenTxResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let token = self.resolve_token_address(chain, &input.token).await?;
+        let owner = self.resolve_address(chain, &input.owner).await?;
+        let spender = self.resolve_address(chain, &input.spender).await?;
+
+        let amount = match (input.amount, input.unlimited.unwrap_or(false)) {
+            (Some(amount), false) => {
+                let unit = input.amount_unit.unwrap_or(AmountUnit::Wei);
+                self.parse_amount(chain, "amount", &amount, unit, Some(token))
+                    .await?
+            }
+            (None, true) => {
+                let (symbol, decimals) = self.denomination(chain, Some(token)).await?;
+                Amount::new(U256::MAX, symbol, decimals)
+            }
+            (Some(_), true) => {
+                return Err(ToolError::invalid_params(
+                    "amount",
+                    "an amount can't be given with an unlimited approval",
+                )
+                .into());
+            }
+            (None, false) => {
+                return Err(ToolError::invalid_params(
+                    "amount",
+                    "an amount is required unless the approval is unlimited",
+                )
+                .into());
+            }
+        };
+
+        self.set_allowance(chain, token, owner, spender, amount)
+            .await
+    }
+
+    /// allowance
+    ///
+    ///     Description:
+    ///         Reads how many tokens a spender may still move on behalf of an owner.
+    ///
+    async fn allowance(&self, input: AllowanceInput) -> Result<AllowanceResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let token = self.resolve_token_address(chain, &input.token).await?;
+        let owner = self.resolve_address(chain, &input.owner).await?;
+        let spender = self.resolve_address(chain, &input.spender).await?;
+
+        let allowance = ERC20::new(token, chain.provider.clone())
+            .allowance(owner, spender)
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let (symbol, decimals) = self.denomination(chain, Some(token)).await?;
+
+        Ok(AllowanceResult {
+            chain: chain.config.name.clone(),
+            token: self.address_info(chain, token).await,
+            owner: self.address_info(chain, owner).await,
+            spender: self.address_info(chain, spender).await,
+            allowance: Amount::new(allowance, symbol, decimals),
+            unlimited: allowance == U256::MAX,
+        })
+    }
+
+    /// revoke
+    ///
+    ///     Description:
+    ///         Sets the allowance of a spender back to zero.
+    ///
+    async fn revoke(&self, input: AllowanceInput) -> Result<TokenTxResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let token = self.resolve_token_address(chain, &input.token).await?;
+        let owner = self.resolve_address(chain, &input.owner).await?;
+        let spender = self.resolve_address(chain, &input.spender).await?;
+        let (symbol, decimals) = self.denomination(chain, Some(token)).await?;
+
+        self.set_allowance(
+            chain,
+            token,
+            owner,
+            spender,
+            Amount::new(U256::zero(), symbol, decimals),
+        )
+        .await
+    }
+}
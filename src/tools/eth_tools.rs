@@ -17,46 +17,60 @@
 //! - [`send`]: Transfer ETH between addresses with transaction confirmation
 //! - [`get_contract`]: Check contract deployment status and bytecode size
 //! - [`get_erc20_balance`]: Query ERC20 token balances using standard interface
+//! - [`list_chains`]: List the chains the server is configured for
+//!
+//! Every operation takes an optional chain selector (name or chain id) and runs against the
+//! default chain when it is omitted.
 //!
 //! # Usage Examples
 //!
 //! ```rust
 //! // Check ETH balance
-//! let balance = multi_tool.get_balance("0x742d35Cc6aF4...".to_string()).await?;
+//! let balance = multi_tool.get_balance("0x742d35Cc6aF4...".to_string(), None).await?;
 //!
 //! // Send ETH transaction
 //! let tx_hash = multi_tool.send(
 //!     "0x742d35Cc6aF4...".to_string(), // from
 //!     "0xd8dA6BF26964aF9D...".to_string(), // to
-//!     "1.5".to_string() // amount in ETH
+//!     "1.5".to_string(), // amount
+//!     Some(AmountUnit::Ether), // unit of the amount, ether when omitted
+//!     Some("base".to_string()) // chain
 //! ).await?;
 //!
 //! // Check ERC20 token balance
 //! let token_balance = multi_tool.get_erc20_balance(
 //!     "0xA0b86a33E6441...".to_string(), // token contract
-//!     "0x742d35Cc6aF4...".to_string()  // account address
+//!     "0x742d35Cc6aF4...".to_string(), // account address
+//!     None // default chain
 //! ).await?;
 //! ```
 //!
 //! # Features
 //!
-//! - **Automatic wallet management**: Falls back to default wallet when sender wallet not found
-//! - **ENS support**: Accepts both addresses and ENS names as input
-//! - **Comprehensive error handling**: Detailed error messages with context
+//! - **Managed wallets**: ETH is sent from managed accounts only, other senders are rejected
+//! - **ENS support**: Accepts both addresses and ENS names as input, and shows the primary ENS
+//!   name of addresses in results
+//! - **Comprehensive error handling**: Failures are classified as [`ToolError`]s so the agent can
+//!   tell bad input from reverts and RPC outages
 //! - **Standard compliance**: Full ERC20 interface implementation
 //! - **Transaction receipts**: Returns confirmation details for all transactions
+//! - **Structured results**: Every operation returns a typed result, see [`crate::tools::results`]
 //!
 //! # Dependencies
 //!
 //! Built on top of the `ethers` crate for robust Ethereum interaction, with automatic
 //! type conversions and gas management for seamless blockchain operations.
+use crate::common::error::ToolError;
 use crate::tools::MultiTool;
+use crate::tools::results::{AddressInfo, BalanceResult, TxResult};
 use crate::tools::traits::EvmTools;
+use crate::tools::units::AmountUnit;
 use anyhow::{Result, anyhow};
 use ethers::prelude::*;
-use ethers::utils::parse_ether;
+use ethers::utils::to_checksum;
 use rmcp::schemars;
-use std::str::FromStr;
+use serde::Serialize;
+use std::fmt::{self, Display};
 
 // Generate ERC20 contract bindings - standard erc20 contract methods
 abigen!(
@@ -80,39 +94,57 @@ abigen!(
 ///
 ///     Fields:
 ///         addr (String): The address of the account to query the balance for
+///         chain (Option<String>): The chain to query, defaults to the default chain
 ///
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct BalanceInput {
     #[schemars(description = "The address or ENS name to check the balance for")]
     pub addr: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
 }
 
 /// Send input struct
 ///
 ///     Fields:
-///         sender (String): The sender address of the account to send ETH from
+///         sender (String): The managed account to send ETH from
 ///         receiver (String): The receiver address
 ///         amount (String): The amount of ETH to send from sender to receiver
+///         amount_unit (Option<AmountUnit>): Unit of the amount, defaults to ether
+///         chain (Option<String>): The chain to send on, defaults to the default chain
 ///
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct SendInput {
-    #[schemars(description = "The address or ENS name used to send ETH from")]
+    #[schemars(description = "The managed account, address or ENS name, used to send ETH from")]
     pub sender: String,
     #[schemars(description = "The address or ENS name to send ETH to")]
     pub receiver: String,
-    #[schemars(description = "The amount of ETH to send")]
+    #[schemars(description = "The amount of ETH to send, in `amount_unit`")]
     pub amount: String,
+    #[schemars(description = "Unit of `amount`: wei, gwei, ether or token, defaults to ether")]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
 }
 
 /// GetContract input struct
 ///
 ///     Fields:
 ///         address (String): contract address to check
+///         chain (Option<String>): The chain to look on, defaults to the default chain
 ///
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GetContractInput {
-    #[schemars(description = "The address of the contract to look for")]
+    #[schemars(description = "The address or ENS name of the contract to look for")]
     pub addr: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
 }
 
 /// ERC20Balance input struct
@@ -120,13 +152,136 @@ pub struct GetContractInput {
 ///     Fields:
 ///         erc20_addr (String): contract address of the erc20 token
 ///         account (String): the account whose balance is being queried
+///         chain (Option<String>): The chain to query, defaults to the default chain
 ///
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct ERC20BalanceInput {
-    #[schemars(description = "The address of the ERC20 contract to look for")]
+    #[schemars(
+        description = "The address, ENS name or token list symbol of the ERC20 contract to look for"
+    )]
     pub erc20_addr: String,
-    #[schemars(description = "The address of the account to get the balance for")]
+    #[schemars(description = "The address or ENS name of the account to get the balance for")]
     pub account: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Deployment status of a contract address.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ContractResult {
+    #[schemars(description = "Name of the chain the contract was looked up on")]
+    pub chain: String,
+    #[schemars(description = "Contract address")]
+    pub contract: AddressInfo,
+    #[schemars(description = "Whether code is deployed at the address")]
+    pub deployed: bool,
+    #[schemars(description = "Size of the deployed code in bytes")]
+    pub code_size: usize,
+}
+
+impl Display for ContractResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.deployed {
+            write!(
+                f,
+                "Contract {} is deployed on {} (code size: {})",
+                self.contract, self.chain, self.code_size
+            )
+        } else {
+            write!(
+                f,
+                "Contract {} is not deployed on {}",
+                self.contract, self.chain
+            )
+        }
+    }
+}
+
+impl MultiTool {
+    /// Size of the code deployed at an address, zero for externally owned accounts
+    pub(crate) async fn code_size(
+        &self,
+        chain: &crate::common::chains::Chain,
+        address: Address,
+    ) -> Result<usize> {
+        let code = chain
+            .provider
+            .get_code(address, None)
+            .await
+            .map_err(ToolError::from_rpc)?;
+        Ok(code.len())
+    }
+}
+
+/// A configured chain as listed to the agent, the RPC URL is left out since it may hold API keys.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ChainInfo {
+    pub name: String,
+    pub chain_id: u64,
+    pub native_symbol: String,
+    pub wrapped_native: Option<String>,
+    pub uniswap_router: Option<String>,
+    pub uniswap_v3_router: Option<String>,
+    pub uniswap_v3_quoter: Option<String>,
+    pub explorer_url: Option<String>,
+    #[schemars(description = "Name of the token list whose symbols token inputs accept, if any")]
+    pub token_list: Option<String>,
+    #[schemars(description = "Number of tokens of the chain in its token list")]
+    pub token_count: usize,
+    #[schemars(description = "Whether this chain is used when a tool call doesn't select one")]
+    pub default: bool,
+}
+
+impl Display for ChainInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (chain id: {}, native: {}",
+            self.name, self.chain_id, self.native_symbol
+        )?;
+        if let Some(weth) = &self.wrapped_native {
+            write!(f, ", wrapped native: {weth}")?;
+        }
+        if let Some(router) = &self.uniswap_router {
+            write!(f, ", uniswap router: {router}")?;
+        }
+        if let Some(router) = &self.uniswap_v3_router {
+            write!(f, ", uniswap v3 router: {router}")?;
+        }
+        if let Some(quoter) = &self.uniswap_v3_quoter {
+            write!(f, ", uniswap v3 quoter: {quoter}")?;
+        }
+        if let Some(explorer) = &self.explorer_url {
+            write!(f, ", explorer: {explorer}")?;
+        }
+        if let Some(list) = &self.token_list {
+            write!(f, ", token list: {list} ({} tokens)", self.token_count)?;
+        }
+        f.write_str(")")?;
+        if self.default {
+            f.write_str(" [default]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Chains the server is configured for.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ChainList {
+    pub chains: Vec<ChainInfo>,
+}
+
+impl Display for ChainList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .chains
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        f.write_str(&lines.join("\n"))
+    }
 }
 
 /// Trait implementation of EvmTools for MultiTool
@@ -139,64 +294,80 @@ impl EvmTools for MultiTool {
     ///     Description:
     ///         Queries the ETH balance of an address
     ///
-    async fn get_balance(&self, address: String) -> Result<String> {
-        let addr = Address::from_str(&address)?;
-        let balance = self
-            .eth_provider
+    async fn get_balance(&self, address: String, chain: Option<String>) -> Result<BalanceResult> {
+        let chain = self.chain(chain.as_deref())?;
+        let addr = self.resolve_address(chain, &address).await?;
+        let balance = chain
+            .provider
             .get_balance(addr, None)
             .await
-            .map_err(|e| {
-                // Add tracing
-                anyhow!("failed to get balance from {}: {}", address, e.to_string())
-            })?;
-        Ok(balance.to_string())
+            .map_err(ToolError::from_rpc)?;
+        Ok(BalanceResult::new(
+            chain,
+            self.address_info(chain, addr).await,
+            None,
+            balance,
+            chain.config.native_symbol.clone(),
+            18,
+        ))
     }
 
     /// send
     ///
     ///     Description:
     ///         Builds a transaction to send ETH from one address to another, signs, executes and
-    ///         returns the transaction hash.
+    ///         returns the mined transaction.
     ///
-    async fn send(&self, from: String, to: String, amount: String) -> Result<String> {
-        let sender = Address::from_str(&from)?;
-        let receiver = Address::from_str(&to)?;
-        let amount = parse_ether(&amount)?;
-
-        //Attempt to get specified sender wallet. If not provided or found, use default wallet.
-        let wallet = match self.accounts.get_wallet(&sender) {
-            None => {
-                if let Some(acc) = self.accounts.default_wallet() {
-                    Ok(acc)
-                } else {
-                    Err(anyhow!("sender not found, failed to get default wallet"))
-                }
-            }
-            Some(acc) => Ok(acc),
-        }?;
+    async fn send(
+        &self,
+        from: String,
+        to: String,
+        amount: String,
+        unit: Option<AmountUnit>,
+        chain: Option<String>,
+    ) -> Result<TxResult> {
+        let chain = self.chain(chain.as_deref())?;
+        let sender = self.resolve_address(chain, &from).await?;
+        let receiver = self.resolve_address(chain, &to).await?;
+        let amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &amount,
+                unit.unwrap_or(AmountUnit::Ether),
+                None,
+            )
+            .await?;
+
+        // Sign with the managed wallet of the sender, other accounts are rejected
+        let wallet = self.managed_wallet(sender)?;
+
+        // Serialize transactions from this account so concurrent sends don't race on the nonce
+        let _guard = self.signer_locks.lock(wallet.address()).await;
 
-        // Initialize client
-        let client = SignerMiddleware::new(&self.eth_provider, wallet.clone());
+        // Initialize client, signing for the selected chain
+        let client = self.signer(chain, wallet);
         let tx = TransactionRequest::new()
             .to(NameOrAddress::Address(receiver))
-            .value(amount);
+            .value(amount.value());
 
         // Send transaction
         let pending_tx = client
             .send_transaction(tx, None)
             .await
-            .map_err(|e| anyhow!("send transaction failed {e}"))?;
+            .map_err(ToolError::from_rpc)?;
         let receipt = pending_tx
             .await
-            .map_err(|e| anyhow!("send transaction failed {e}"))?;
+            .map_err(ToolError::from_rpc)?
+            .ok_or_else(|| anyhow!("receipt was empty"))?;
 
-        // Check receipt and return tx hash if it's ok
-        let tx_hash = match receipt {
-            None => Err(anyhow!("receipt was empty")),
-            Some(r) => Ok(r.transaction_hash),
-        }?;
-
-        Ok(format!("Transaction Successful! Hash: {tx_hash:x}"))
+        Ok(TxResult::new(
+            chain,
+            &receipt,
+            self.address_info(chain, wallet.address()).await,
+            Some(self.address_info(chain, receiver).await),
+        )
+        .with_value(amount))
     }
 
     /// get_contract
@@ -205,17 +376,20 @@ impl EvmTools for MultiTool {
     ///         Queries a contract address by retrieving its code in order to determine whether
     ///         it was deployed.
     ///
-    async fn get_contract(&self, contract: String) -> Result<String> {
-        let contract_addr = Address::from_str(&contract)?;
-        let code = self.eth_provider.get_code(contract_addr, None).await?;
-        if !code.is_empty() {
-            Ok(format!(
-                "Contract {contract} is deployed (code size: {})",
-                code.len()
-            ))
-        } else {
-            Ok(format!("Contract {contract} is not deployed"))
-        }
+    async fn get_contract(
+        &self,
+        contract: String,
+        chain: Option<String>,
+    ) -> Result<ContractResult> {
+        let chain = self.chain(chain.as_deref())?;
+        let contract_addr = self.resolve_address(chain, &contract).await?;
+        let code_size = self.code_size(chain, contract_addr).await?;
+        Ok(ContractResult {
+            chain: chain.config.name.clone(),
+            contract: self.address_info(chain, contract_addr).await,
+            deployed: code_size > 0,
+            code_size,
+        })
     }
 
     /// get_erc20_balance
@@ -223,16 +397,75 @@ impl EvmTools for MultiTool {
     ///     Description:
     ///         Queries the balance of an account associated with an ERC20 token
     ///
-    async fn get_erc20_balance(&self, contract: String, account: String) -> Result<String> {
-        // Convert strings to addresses
-        let token_addr = Address::from_str(&contract)?;
-        let account_addr = Address::from_str(&account)?;
+    async fn get_erc20_balance(
+        &self,
+        contract: String,
+        account: String,
+        chain: Option<String>,
+    ) -> Result<BalanceResult> {
+        let chain = self.chain(chain.as_deref())?;
+
+        // Resolve addresses and ENS names
+        let token_addr = self.resolve_token_address(chain, &contract).await?;
+        let account_addr = self.resolve_address(chain, &account).await?;
 
         // Get contract (cloning the atomic reference counter)
-        let contract = ERC20::new(token_addr, self.eth_provider.clone());
+        let contract = ERC20::new(token_addr, chain.provider.clone());
 
-        // get balance
-        let balance = contract.balance_of(account_addr).call().await?;
-        Ok(format!("{balance}"))
+        // get balance along with the token's denomination
+        let balance = contract
+            .balance_of(account_addr)
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let decimals = contract
+            .decimals()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let symbol = contract
+            .symbol()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        Ok(BalanceResult::new(
+            chain,
+            self.address_info(chain, account_addr).await,
+            Some(self.address_info(chain, token_addr).await),
+            balance,
+            symbol,
+            decimals,
+        ))
+    }
+
+    /// list_chains
+    ///
+    ///     Description:
+    ///         Lists the configured chains that can be selected by name or chain id, marking the
+    ///         default chain.
+    ///
+    async fn list_chains(&self) -> Result<ChainList> {
+        let default = &self.chains.default_chain().config.name;
+        let chains = self
+            .chains
+            .iter()
+            .map(|chain| {
+                let config = &chain.config;
+                ChainInfo {
+                    name: config.name.clone(),
+                    chain_id: config.chain_id,
+                    native_symbol: config.native_symbol.clone(),
+                    wrapped_native: config.wrapped_native.map(|a| to_checksum(&a, None)),
+                    uniswap_router: config.uniswap_router.map(|a| to_checksum(&a, None)),
+                    uniswap_v3_router: config.uniswap_v3_router.map(|a| to_checksum(&a, None)),
+                    uniswap_v3_quoter: config.uniswap_v3_quoter.map(|a| to_checksum(&a, None)),
+                    explorer_url: config.explorer_url.clone(),
+                    token_list: config.tokens.name.clone(),
+                    token_count: config.tokens.tokens.len(),
+                    default: &config.name == default,
+                }
+            })
+            .collect();
+        Ok(ChainList { chains })
     }
 }
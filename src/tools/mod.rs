@@ -6,68 +6,204 @@
 //!
 //! ## Components
 //!
-//! - **Ethereum Provider**: Connection to Ethereum RPC endpoint for blockchain operations
+//! - **Chain Registry**: Providers and metadata for every configured EVM chain
 //! - **Account Management**: Handles account-related functionality
-//! - **Brave Search Integration**: Provides web search capabilities via Brave API
-//! - **0x Protocol Integration**: Enables decentralized exchange functionality
+//! - **Signer Locks**: Serializes transactions per sending account so nonces stay sequential
+//! - **Brave Search Integration**: Provides web search capabilities via Brave API (optional)
+//! - **0x Protocol Integration**: Enables decentralized exchange functionality (optional)
+//!
+//! Optional integrations are only set up when configured, [`ToolGroup`] describes which groups of
+//! tools are available so the server only exposes those.
 //!
 //! ## Submodules
 //!
 //! - `agent_mcp`: MCP (Model Context Protocol) agent functionality
 //! - `brave_tools`: Brave search API integration tools
+//! - `ens`: ENS name resolution for address inputs and results
+//! - `erc20_tools`: ERC20 transfers, approvals and allowances
 //! - `eth_tools`: Ethereum blockchain interaction utilities
+//! - `token_list_tools`: Token symbol resolution and search from the chains' token lists
 //! - `zero_x_tools`: 0x protocol integration for DEX operations
 //! - `uniswap_tools`: Uniswap protocol integration tools
+//! - `uniswap_v3_tools`: Uniswap V3 swaps and quotes through the SwapRouter and QuoterV2
+//! - `prompts`: MCP prompts for common DeFi workflows
+//! - `resources`: MCP resources for accounts, chain state and token metadata
+//! - `results`: Typed tool results shared by the tool modules
 //! - `traits`: Common traits and interfaces
+//! - `units`: Amount units accepted by amount inputs
 //!
 //! ## Constants
 //!
 //! - `DEFAULT_ETH_TOKEN_ADDRESS`: Default Ethereum token address (ETH placeholder)
-//! - `WETH_TOKEN_ADDRESS`: Wrapped Ethereum (WETH) contract address on mainnet
+//!
+//! Chain specific addresses such as WETH and the Uniswap router live in the chain registry.
 //!
 //! ## Usage
 //!
 //! ```rust
-//! let config = Config::load();
-//! let multitool = MultiTool::new(&config);
+//! let config = Config::load(None)?;
+//! let multitool = MultiTool::new(&config)?;
 //! // Use multitool for various blockchain and web operations
 //! ```
-use ethers::prelude::{Http, Provider};
+use anyhow::{Result, anyhow};
+use ethers::prelude::{Address, Http, LocalWallet, Provider, Signer, SignerMiddleware};
+use std::fmt::{self, Display};
 use std::sync::Arc;
 
-use crate::common::accounts::Accounts;
-use crate::common::context::Config;
+use crate::common::accounts::{Accounts, SignerLocks};
+use crate::common::chains::{Chain, ChainRegistry};
+use crate::common::context::{Config, UniswapConfig};
+use crate::common::error::ToolError;
 use crate::tools::brave_tools::BraveContext;
 use crate::tools::zero_x_tools::ZeroXContext;
 
 pub mod agent_mcp;
 mod brave_tools;
+mod ens;
+mod erc20_tools;
 mod eth_tools;
+mod token_list_tools;
 mod zero_x_tools;
 
+mod prompts;
+mod resources;
+pub mod results;
 pub mod traits;
 mod uniswap_tools;
+mod uniswap_v3_tools;
+pub mod units;
 
 const DEFAULT_ETH_TOKEN_ADDRESS: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
-const WETH_TOKEN_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+/// Client signing transactions with a managed wallet
+pub(crate) type SignerClient = SignerMiddleware<Arc<Provider<Http>>, LocalWallet>;
+
+/// Groups of tools exposed by the server, each backed by one integration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolGroup {
+    Eth,
+    Brave,
+    ZeroX,
+    Uniswap,
+    UniswapV3,
+    TokenList,
+}
+
+impl Display for ToolGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ToolGroup::Eth => "eth_tools",
+            ToolGroup::Brave => "brave_tools",
+            ToolGroup::ZeroX => "zero_x_tools",
+            ToolGroup::Uniswap => "uniswap_tools",
+            ToolGroup::UniswapV3 => "uniswap_v3_tools",
+            ToolGroup::TokenList => "token_list_tools",
+        };
+        f.write_str(name)
+    }
+}
 
 pub struct MultiTool {
-    pub eth_provider: Arc<Provider<Http>>,
+    pub chains: ChainRegistry,
     pub accounts: Accounts,
-    pub brave_ctx: BraveContext,
-    pub zero_x_context: ZeroXContext,
+    pub signer_locks: SignerLocks,
+    pub brave_ctx: Option<BraveContext>,
+    pub zero_x_context: Option<ZeroXContext>,
+    pub uniswap: UniswapConfig,
 }
 
 impl MultiTool {
-    pub fn new(cfg: &Config) -> Self {
-        tracing::info!("Creating ETH provider");
-        let provider = Provider::<Http>::try_from(cfg.eth_rpc.clone())
-            .expect("should build provider to local eth node");
-        Self {
-            eth_provider: Arc::new(provider),
-            accounts: Accounts::default(),
-            brave_ctx: BraveContext::new(cfg.brave_api_key.clone()),
-            zero_x_context: ZeroXContext::new(cfg.zero_x_api_key.clone()),
+    pub fn new(cfg: &Config) -> Result<Self> {
+        Ok(Self {
+            chains: ChainRegistry::new(&cfg.chains, &cfg.default_chain)?,
+            accounts: Accounts::from_mnemonic(&cfg.accounts.mnemonic, cfg.accounts.count)?,
+            signer_locks: SignerLocks::default(),
+            brave_ctx: cfg
+                .brave
+                .as_ref()
+                .map(|brave| BraveContext::new(brave.api_key.clone(), brave.result_count)),
+            zero_x_context: cfg
+                .zero_x
+                .as_ref()
+                .map(|zero_x| ZeroXContext::new(zero_x.api_key.clone())),
+            uniswap: cfg.uniswap.clone(),
+        })
+    }
+
+    /// Tool groups that are configured and can be exposed by the server
+    pub fn enabled_groups(&self) -> Vec<ToolGroup> {
+        let mut groups = vec![ToolGroup::Eth];
+        if self.brave_ctx.is_some() {
+            groups.push(ToolGroup::Brave);
+        }
+        if self.zero_x_context.is_some() {
+            groups.push(ToolGroup::ZeroX);
+        }
+        if self
+            .chains
+            .iter()
+            .any(|chain| chain.config.uniswap_router.is_some())
+        {
+            groups.push(ToolGroup::Uniswap);
+        }
+        if self.chains.iter().any(|chain| {
+            chain.config.uniswap_v3_router.is_some() || chain.config.uniswap_v3_quoter.is_some()
+        }) {
+            groups.push(ToolGroup::UniswapV3);
         }
+        if self
+            .chains
+            .iter()
+            .any(|chain| chain.config.tokens.name.is_some())
+        {
+            groups.push(ToolGroup::TokenList);
+        }
+        groups
+    }
+
+    /// Enabled tool groups formatted for display, e.g. `eth_tools | brave_tools`
+    pub fn enabled_groups_summary(&self) -> String {
+        self.enabled_groups()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Wallet of a managed account, accounts the server holds no key for are rejected
+    pub(crate) fn managed_wallet(&self, account: Address) -> Result<&LocalWallet> {
+        self.accounts.get_wallet(&account).ok_or_else(|| {
+            ToolError::NotFound {
+                what: String::from("managed account"),
+                name: format!("{account:?}"),
+                detail: Some(String::from("see accounts://managed for the accounts")),
+            }
+            .into()
+        })
+    }
+
+    /// Client signing transactions with the wallet on the selected chain
+    pub(crate) fn signer(&self, chain: &Chain, wallet: &LocalWallet) -> SignerClient {
+        SignerMiddleware::new(
+            chain.provider.clone(),
+            wallet.clone().with_chain_id(chain.config.chain_id),
+        )
+    }
+
+    /// Chain selected by name or chain id, the default chain when no selector is given
+    fn chain(&self, selector: Option<&str>) -> Result<&Chain> {
+        self.chains.get(selector)
+    }
+
+    fn brave(&self) -> Result<&BraveContext> {
+        self.brave_ctx
+            .as_ref()
+            .ok_or_else(|| anyhow!("brave tools are not configured"))
+    }
+
+    fn zero_x(&self) -> Result<&ZeroXContext> {
+        self.zero_x_context
+            .as_ref()
+            .ok_or_else(|| anyhow!("0x tools are not configured"))
     }
 }
@@ -0,0 +1,204 @@
+//! MCP prompts for common DeFi workflows.
+//!
+//! Each prompt is a parameterized template walking the agent through a vetted multi-step
+//! procedure using the server's tools, so it doesn't improvise on operations that move funds:
+//!
+//! - **`audit_address`**: Inspect an address, its code, balances and token metadata
+//! - **`find_token_contract`**: Find a token's contract address on the web and verify it on-chain
+//! - **`safe_swap`**: Check balances, quote and execute a swap
+//!
+//! Like tools, prompts are grouped by the tool groups they rely on and only listed when those
+//! groups are enabled.
+use rmcp::handler::server::{router::prompt::PromptRouter, wrapper::Parameters};
+use rmcp::model::{GetPromptResult, PromptMessage, PromptMessageRole};
+use rmcp::{prompt, prompt_router, schemars};
+
+use crate::tools::ToolGroup;
+use crate::tools::agent_mcp::AgentMcpServer;
+
+/// Audit address prompt arguments
+///
+///     Fields:
+///         address (String): The address or ENS name to audit
+///         chain (Option<String>): The chain to audit on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AuditAddressArgs {
+    #[schemars(description = "The address or ENS name to audit")]
+    pub address: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Find token contract prompt arguments
+///
+///     Fields:
+///         token (String): Name or symbol of the token to look for
+///         chain (Option<String>): The chain the contract must be deployed on
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct FindTokenArgs {
+    #[schemars(description = "Name or symbol of the token to look for, e.g. USDC")]
+    pub token: String,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Safe swap prompt arguments
+///
+///     Fields:
+///         amount (String): The amount of the sold token, in whole units
+///         from_token (String): Token being sold, ETH or a token address
+///         to_token (String): Token being bought, ETH or a token address
+///         account (Option<String>): The account swapping, defaults to the default account
+///         chain (Option<String>): The chain to swap on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SafeSwapArgs {
+    #[schemars(description = "The amount of the sold token, in whole units (e.g. 1.5)")]
+    pub amount: String,
+    #[schemars(
+        description = "Token being sold, ETH or a token address, ENS name or token list symbol"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "Token being bought, ETH or a token address, ENS name or token list symbol"
+    )]
+    pub to_token: String,
+    #[schemars(description = "The address or ENS name swapping, defaults to the first account")]
+    pub account: Option<String>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Wording used in prompts for an optional chain selector
+fn on_chain(chain: &Option<String>) -> String {
+    match chain {
+        Some(chain) => format!("on chain `{chain}` (pass it as `chain` to every tool)"),
+        None => String::from("on the default chain"),
+    }
+}
+
+/// Builds a prompt made of a single user message
+fn user_prompt(description: &str, text: String) -> GetPromptResult {
+    GetPromptResult {
+        description: Some(description.to_string()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    }
+}
+
+impl AgentMcpServer {
+    /// Prompt router holding the prompts whose tool groups are all enabled
+    pub(crate) fn enabled_prompt_router(groups: &[ToolGroup]) -> PromptRouter<Self> {
+        let mut router = PromptRouter::new();
+        if groups.contains(&ToolGroup::Eth) {
+            router += Self::eth_prompt_router();
+        }
+        if groups.contains(&ToolGroup::Brave) {
+            router += Self::brave_prompt_router();
+        }
+        if groups.contains(&ToolGroup::ZeroX) && groups.contains(&ToolGroup::Uniswap) {
+            router += Self::swap_prompt_router();
+        }
+        router
+    }
+}
+
+#[prompt_router(router = "eth_prompt_router")]
+impl AgentMcpServer {
+    #[prompt(description = "Audit an address: contract code, balances and token metadata")]
+    async fn audit_address(
+        &self,
+        Parameters(args): Parameters<AuditAddressArgs>,
+    ) -> GetPromptResult {
+        let text = format!(
+            "Audit the address `{address}` {chain}. Follow these steps in order:\n\
+             1. Call `get_contract` to tell whether it is a contract or an externally owned account, \
+             and note its ENS name if one is shown.\n\
+             2. Call `balance` to get its ETH balance.\n\
+             3. If it is a contract, read the `token://{address}` resource to check whether it is an \
+             ERC20 token and get its name, symbol, decimals and total supply.\n\
+             4. For any token the user cares about, call `get_erc20_balance` with the address as \
+             the account.\n\
+             5. Summarize what the address is, its balances and anything unusual, such as a claimed \
+             contract without code or an ENS name that doesn't match.\n\
+             Only report values returned by the tools, never estimate them.",
+            address = args.address,
+            chain = on_chain(&args.chain),
+        );
+        user_prompt("Audit an address", text)
+    }
+}
+
+#[prompt_router(router = "brave_prompt_router")]
+impl AgentMcpServer {
+    #[prompt(description = "Find a token's contract address on the web and verify it on-chain")]
+    async fn find_token_contract(
+        &self,
+        Parameters(args): Parameters<FindTokenArgs>,
+    ) -> GetPromptResult {
+        let text = format!(
+            "Find the contract address of the token `{token}` {chain}. Follow these steps in order:\n\
+             1. Call `web_search` with a query like \"{token} token contract address\" and the \
+             chain name. Each address it returns comes with the results mentioning it, whether \
+             it has code on-chain and its ERC20 symbol, most mentioned first.\n\
+             2. Discard any address without deployed code or whose symbol doesn't match \
+             `{token}`, and prefer addresses mentioned by several independent sites.\n\
+             3. Read the `token://<address>` resource of each remaining candidate and keep only \
+             those whose symbol and name match `{token}`.\n\
+             4. If exactly one candidate remains, report it with its name, symbol and decimals. If \
+             several remain or none do, say so and list them instead of picking one.\n\
+             Never guess or construct an address, only report addresses verified on-chain.",
+            token = args.token,
+            chain = on_chain(&args.chain),
+        );
+        user_prompt("Find and verify a token contract", text)
+    }
+}
+
+#[prompt_router(router = "swap_prompt_router")]
+impl AgentMcpServer {
+    #[prompt(description = "Swap an amount of one token for another safely: check, quote, swap")]
+    async fn safe_swap(&self, Parameters(args): Parameters<SafeSwapArgs>) -> GetPromptResult {
+        let account = match &args.account {
+            Some(account) => format!("the account `{account}`"),
+            None => String::from("the first managed account (see `accounts://managed`)"),
+        };
+        let text = format!(
+            "Swap {amount} `{from}` for `{to}` from {account} {chain}. Follow these steps in \
+             order and stop as soon as one fails or looks wrong:\n\
+             1. Unless a token is ETH, call `get_contract` on it and make sure code is deployed.\n\
+             2. Check that the account holds at least {amount} `{from}`: call `balance` when \
+             selling ETH, leaving room for gas, or `get_erc20_balance` otherwise.\n\
+             3. Call `get_quote` with `amount` {amount} and `amount_unit` `token`, and compare \
+             its `buy_amount` with the on-chain `amount_out` of `get_uniswap_quote` for the same \
+             amount. If `get_uniswap_v3_quote` is available, quote the same amount with it too \
+             and when it returns more, swap with `swap_uniswap_v3` in step 4 instead, passing \
+             {amount} as `amount` with `amount_unit` `token` and the quoted `fees`. Tell the \
+             user the expected output and the price impact before swapping, and stop if the \
+             quotes differ by more than a few percent.\n\
+             4. Swap with `swap_eth_for_tokens` when selling ETH, `swap_tokens_for_eth` when \
+             buying ETH or `swap_tokens_for_tokens` otherwise, passing {amount} as `amount_in` \
+             with `amount_in_unit` `token`, the `path` of the Uniswap quote's intermediate tokens \
+             as `via` for token to token swaps, and no `min_amount_out`, so the server quotes the \
+             output on-chain and lowers it by the configured slippage tolerance. When 0x quoted \
+             more than Uniswap, swap with `swap_0x` instead, passing {amount} as `amount` with \
+             `amount_unit` `token`. The router, or Permit2 for 0x, is approved for the exact \
+             amount sold when needed.\n\
+             5. Report the transaction hash, status and gas used from the result, and the \
+             hashes of the allowance reset and the approval when they were sent.\n\
+             Never retry a failed swap without asking the user first.",
+            amount = args.amount,
+            from = args.from_token,
+            to = args.to_token,
+            chain = on_chain(&args.chain),
+        );
+        user_prompt("Swap tokens safely", text)
+    }
+}
@@ -0,0 +1,213 @@
+//! MCP resources exposing read-only context to the agent.
+//!
+//! Resources let an agent read context without spending tool calls:
+//!
+//! - `accounts://managed`: Addresses and derivation paths of the managed accounts, keys are never
+//!   exposed
+//! - `chain://head`: Latest block of the default chain (block number, base fee, chain id)
+//! - `chain://head{?chain}`: Latest block of a chain selected by name or chain id
+//! - `token://{address}{?chain}`: ERC20 name, symbol, decimals and total supply of a token, the
+//!   address may be an ENS name
+//!
+//! Every resource is returned as JSON.
+use crate::common::error::ToolError;
+use crate::tools::MultiTool;
+use crate::tools::eth_tools::ERC20;
+use crate::tools::results::AddressInfo;
+use crate::tools::traits::ResourceTools;
+use anyhow::Result;
+use ethers::prelude::*;
+use ethers::utils::{format_units, to_checksum};
+use serde::Serialize;
+
+pub const ACCOUNTS_URI: &str = "accounts://managed";
+pub const CHAIN_HEAD_URI: &str = "chain://head";
+pub const CHAIN_HEAD_TEMPLATE: &str = "chain://head{?chain}";
+pub const TOKEN_TEMPLATE: &str = "token://{address}{?chain}";
+
+const TOKEN_SCHEME: &str = "token://";
+
+/// A resource URI the server knows how to read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResourceUri {
+    Accounts,
+    ChainHead {
+        chain: Option<String>,
+    },
+    Token {
+        address: String,
+        chain: Option<String>,
+    },
+}
+
+impl ResourceUri {
+    /// Parses a resource URI, `None` when it doesn't match any resource or template
+    pub fn parse(uri: &str) -> Option<Self> {
+        let (path, query) = match uri.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (uri, None),
+        };
+        // The only query parameter is the chain selector
+        let chain = match query {
+            None => None,
+            Some(query) => Some(query.strip_prefix("chain=")?.to_string()),
+        }
+        .filter(|chain| !chain.is_empty());
+
+        match path {
+            ACCOUNTS_URI if query.is_none() => Some(ResourceUri::Accounts),
+            CHAIN_HEAD_URI => Some(ResourceUri::ChainHead { chain }),
+            _ => {
+                let address = path.strip_prefix(TOKEN_SCHEME)?;
+                (!address.is_empty() && !address.contains('/')).then(|| ResourceUri::Token {
+                    address: address.to_string(),
+                    chain,
+                })
+            }
+        }
+    }
+}
+
+/// A managed account, identified by its derivation path.
+#[derive(Debug, Serialize)]
+pub struct ManagedAccount {
+    pub address: String,
+    pub derivation_path: String,
+}
+
+/// Accounts the server can sign for, the first one is used when no sender is given.
+#[derive(Debug, Serialize)]
+pub struct ManagedAccounts {
+    pub accounts: Vec<ManagedAccount>,
+}
+
+/// Latest block of a chain.
+#[derive(Debug, Serialize)]
+pub struct ChainHead {
+    pub chain: String,
+    pub chain_id: u64,
+    pub block_number: Option<u64>,
+    pub block_hash: Option<String>,
+    pub timestamp: String,
+    /// Base fee per gas in wei, absent on chains without EIP-1559
+    pub base_fee_per_gas: Option<String>,
+}
+
+/// Metadata of an ERC20 token.
+#[derive(Debug, Serialize)]
+pub struct TokenMetadata {
+    pub chain: String,
+    pub token: AddressInfo,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Total supply in base units, as a decimal string
+    pub total_supply: String,
+    /// Total supply formatted with the token's decimals
+    pub total_supply_formatted: String,
+}
+
+impl ResourceTools for MultiTool {
+    async fn managed_accounts(&self) -> Result<ManagedAccounts> {
+        let accounts = self
+            .accounts
+            .ordered()
+            .into_iter()
+            .map(|(address, account)| ManagedAccount {
+                address: to_checksum(address, None),
+                derivation_path: account.derivation_path.clone(),
+            })
+            .collect();
+        Ok(ManagedAccounts { accounts })
+    }
+
+    async fn chain_head(&self, chain: Option<String>) -> Result<ChainHead> {
+        let chain = self.chain(chain.as_deref())?;
+        let block = chain
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await
+            .map_err(ToolError::from_rpc)?
+            .ok_or_else(|| ToolError::not_found("block", "latest"))?;
+
+        Ok(ChainHead {
+            chain: chain.config.name.clone(),
+            chain_id: chain.config.chain_id,
+            block_number: block.number.map(|number| number.as_u64()),
+            block_hash: block.hash.map(|hash| format!("{hash:?}")),
+            timestamp: block.timestamp.to_string(),
+            base_fee_per_gas: block.base_fee_per_gas.map(|fee| fee.to_string()),
+        })
+    }
+
+    async fn token_metadata(
+        &self,
+        address: String,
+        chain: Option<String>,
+    ) -> Result<TokenMetadata> {
+        let chain = self.chain(chain.as_deref())?;
+        let token_addr = self.resolve_address(chain, &address).await?;
+        let contract = ERC20::new(token_addr, chain.provider.clone());
+
+        let name = contract
+            .name()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let symbol = contract
+            .symbol()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let decimals = contract
+            .decimals()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let total_supply = contract
+            .total_supply()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+
+        Ok(TokenMetadata {
+            chain: chain.config.name.clone(),
+            token: self.address_info(chain, token_addr).await,
+            name,
+            symbol,
+            decimals,
+            total_supply: total_supply.to_string(),
+            total_supply_formatted: format_units(total_supply, u32::from(decimals))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        assert_eq!(
+            ResourceUri::parse("accounts://managed"),
+            Some(ResourceUri::Accounts)
+        );
+        assert_eq!(
+            ResourceUri::parse("chain://head?chain=base"),
+            Some(ResourceUri::ChainHead {
+                chain: Some(String::from("base"))
+            })
+        );
+        assert_eq!(
+            ResourceUri::parse("token://usdc.eth"),
+            Some(ResourceUri::Token {
+                address: String::from("usdc.eth"),
+                chain: None
+            })
+        );
+        assert_eq!(ResourceUri::parse("token://"), None);
+        assert_eq!(ResourceUri::parse("chain://head?block=1"), None);
+        assert_eq!(ResourceUri::parse("accounts://other"), None);
+    }
+}
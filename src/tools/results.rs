@@ -0,0 +1,333 @@
+//! Typed tool results.
+//!
+//! Tools return these instead of free-form strings. The server sends each result to the agent as
+//! structured content, matching the tool's output schema, together with a human-readable text
+//! block built from its `Display` implementation.
+//!
+//! Amounts are decimal strings in base units (e.g. wei) so values above `u64` survive JSON, and
+//! come with a formatted value using the token's decimals.
+use crate::common::chains::Chain;
+use crate::tools::uniswap_tools::QuoteSide;
+use ethers::prelude::{Address, TransactionReceipt, U256};
+use ethers::utils::{format_units, to_checksum};
+use rmcp::schemars;
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+/// An address together with its primary ENS name, when one is set.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct AddressInfo {
+    #[schemars(description = "Checksummed address")]
+    pub address: String,
+    #[schemars(description = "Primary ENS name of the address, if any")]
+    pub ens_name: Option<String>,
+}
+
+impl AddressInfo {
+    pub fn new(address: Address, ens_name: Option<String>) -> Self {
+        Self {
+            address: to_checksum(&address, None),
+            ens_name,
+        }
+    }
+}
+
+impl Display for AddressInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.ens_name {
+            Some(name) => write!(f, "{name} ({})", self.address),
+            None => f.write_str(&self.address),
+        }
+    }
+}
+
+/// An amount of the chain's native currency or of an ERC20 token.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct Amount {
+    #[schemars(description = "Amount in base units (wei for ETH), as a decimal string")]
+    pub raw: String,
+    #[schemars(description = "Amount formatted with the token's decimals")]
+    pub formatted: String,
+    #[schemars(description = "Symbol of the token or native currency")]
+    pub symbol: String,
+    #[schemars(description = "Number of decimals of the token or native currency")]
+    pub decimals: u8,
+}
+
+impl Amount {
+    pub fn new(value: U256, symbol: String, decimals: u8) -> Self {
+        Self {
+            raw: value.to_string(),
+            formatted: format_units(value, u32::from(decimals)).unwrap_or_default(),
+            symbol,
+            decimals,
+        }
+    }
+
+    /// Amount in base units
+    pub fn value(&self) -> U256 {
+        U256::from_dec_str(&self.raw).unwrap_or_default()
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({} base units)",
+            self.formatted, self.symbol, self.raw
+        )
+    }
+}
+
+/// Balance of an account, in the chain's native currency or in an ERC20 token.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct BalanceResult {
+    #[schemars(description = "Name of the chain the balance was read on")]
+    pub chain: String,
+    #[schemars(description = "Account holding the balance")]
+    pub account: AddressInfo,
+    #[schemars(description = "ERC20 token contract, absent for the native currency")]
+    pub token: Option<AddressInfo>,
+    #[serde(flatten)]
+    pub balance: Amount,
+}
+
+impl BalanceResult {
+    pub fn new(
+        chain: &Chain,
+        account: AddressInfo,
+        token: Option<AddressInfo>,
+        balance: U256,
+        symbol: String,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            chain: chain.config.name.clone(),
+            account,
+            token,
+            balance: Amount::new(balance, symbol, decimals),
+        }
+    }
+}
+
+impl Display for BalanceResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Balance of {} on {}: {}",
+            self.account, self.chain, self.balance
+        )
+    }
+}
+
+/// Outcome of a mined transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    Success,
+    Reverted,
+}
+
+/// A mined transaction, built from its receipt.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct TxResult {
+    #[schemars(description = "Name of the chain the transaction was sent on")]
+    pub chain: String,
+    #[schemars(description = "Transaction hash")]
+    pub hash: String,
+    #[schemars(description = "Block the transaction was included in")]
+    pub block_number: Option<u64>,
+    #[schemars(description = "Whether the transaction succeeded or reverted")]
+    pub status: TxStatus,
+    #[schemars(description = "Gas used by the transaction")]
+    pub gas_used: Option<String>,
+    #[schemars(description = "Price paid per unit of gas in wei, as a decimal string")]
+    pub effective_gas_price: Option<String>,
+    #[schemars(description = "Account that sent the transaction")]
+    pub from: AddressInfo,
+    #[schemars(description = "Recipient or contract called by the transaction")]
+    pub to: Option<AddressInfo>,
+    #[schemars(description = "Link to the transaction on the chain's block explorer")]
+    pub explorer_url: Option<String>,
+    #[schemars(description = "Native currency sent with the transaction, if any")]
+    pub value: Option<Amount>,
+}
+
+impl TxResult {
+    pub fn new(
+        chain: &Chain,
+        receipt: &TransactionReceipt,
+        from: AddressInfo,
+        to: Option<AddressInfo>,
+    ) -> Self {
+        let status = match receipt.status {
+            Some(status) if status.is_zero() => TxStatus::Reverted,
+            _ => TxStatus::Success,
+        };
+        Self {
+            chain: chain.config.name.clone(),
+            hash: format!("{:?}", receipt.transaction_hash),
+            block_number: receipt.block_number.map(|block| block.as_u64()),
+            status,
+            gas_used: receipt.gas_used.map(|gas| gas.to_string()),
+            effective_gas_price: receipt.effective_gas_price.map(|price| price.to_string()),
+            from,
+            to,
+            explorer_url: chain.tx_url(receipt.transaction_hash),
+            value: None,
+        }
+    }
+
+    /// Records the native currency sent with the transaction
+    pub fn with_value(mut self, value: Amount) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+impl Display for TxResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            TxStatus::Success => write!(f, "Transaction successful! Hash: {}", self.hash)?,
+            TxStatus::Reverted => write!(f, "Transaction reverted! Hash: {}", self.hash)?,
+        }
+        write!(f, ", Chain: {}, From: {}", self.chain, self.from)?;
+        if let Some(to) = &self.to {
+            write!(f, ", To: {to}")?;
+        }
+        if let Some(value) = &self.value {
+            write!(f, ", Value: {value}")?;
+        }
+        if let Some(block) = self.block_number {
+            write!(f, ", Block: {block}")?;
+        }
+        if let Some(gas_used) = &self.gas_used {
+            write!(f, ", Gas used: {gas_used}")?;
+        }
+        if let Some(price) = &self.effective_gas_price {
+            write!(f, ", Effective gas price: {price} wei")?;
+        }
+        if let Some(url) = &self.explorer_url {
+            write!(f, ", Explorer: {url}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A mined swap, with the amount sold and the minimum amount accepted in return.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SwapResult {
+    #[serde(flatten)]
+    pub tx: TxResult,
+    #[schemars(description = "Approval of the router sent before the swap, if one was needed")]
+    pub approval: Option<TxResult>,
+    #[schemars(
+        description = "Approval of 0 sent before the approval to reset a lower non-zero allowance, as tokens such as USDT require"
+    )]
+    pub allowance_reset: Option<TxResult>,
+    #[schemars(description = "Whether the input (exact_in) or the output (exact_out) was fixed")]
+    pub side: QuoteSide,
+    #[schemars(description = "Tokens the swap goes through, ETH is routed as the wrapped token")]
+    pub path: Vec<String>,
+    #[schemars(
+        description = "Fee tier of every pool along the path in hundredths of a bip (3000 = 0.3%), for Uniswap V3 swaps"
+    )]
+    pub fees: Option<Vec<u32>>,
+    #[schemars(
+        description = "Amount sold, or the most that could be sold for exact_out swaps, after slippage"
+    )]
+    pub amount_in: Amount,
+    #[schemars(
+        description = "Minimum amount accepted in return after slippage, or the exact amount bought for exact_out swaps"
+    )]
+    pub min_amount_out: Amount,
+    #[schemars(description = "Slippage tolerance applied to the quoted amount, in basis points")]
+    pub slippage_bps: u32,
+    #[schemars(description = "Unix timestamp after which the swap would have been rejected")]
+    pub deadline: u64,
+}
+
+impl Display for SwapResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.side {
+            QuoteSide::ExactIn => write!(
+                f,
+                "{}, Sold: {}, Min received: {} ({} bps slippage)",
+                self.tx, self.amount_in, self.min_amount_out, self.slippage_bps
+            )?,
+            QuoteSide::ExactOut => write!(
+                f,
+                "{}, Bought: {}, Max sold: {} ({} bps slippage)",
+                self.tx, self.min_amount_out, self.amount_in, self.slippage_bps
+            )?,
+        }
+        if let Some(reset) = &self.allowance_reset {
+            write!(f, ", Allowance reset hash: {}", reset.hash)?;
+        }
+        if let Some(approval) = &self.approval {
+            write!(f, ", Approval hash: {}", approval.hash)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::chains::{ChainLayer, ChainRegistry};
+    use ethers::prelude::{H256, U64};
+
+    fn registry() -> ChainRegistry {
+        let layer = ChainLayer {
+            name: String::from("mainnet"),
+            rpc_url: Some(String::from("http://localhost:8545")),
+            ..Default::default()
+        };
+        let config = layer.resolve(true, &mut Vec::new()).unwrap();
+        ChainRegistry::new(&[config], "mainnet").unwrap()
+    }
+
+    #[test]
+    fn test_balance_is_formatted_with_decimals() {
+        let registry = registry();
+        let account = AddressInfo::new(Address::zero(), Some(String::from("zero.eth")));
+        let balance = BalanceResult::new(
+            registry.default_chain(),
+            account,
+            None,
+            U256::from(1_500_000u64),
+            String::from("USDC"),
+            6,
+        );
+
+        assert_eq!(balance.balance.raw, "1500000");
+        assert_eq!(balance.balance.formatted, "1.500000");
+        assert!(balance.to_string().contains("zero.eth"));
+    }
+
+    #[test]
+    fn test_tx_result_reports_reverted_receipts() {
+        let registry = registry();
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(0xab),
+            block_number: Some(U64::from(42)),
+            status: Some(U64::zero()),
+            ..Default::default()
+        };
+        let tx = TxResult::new(
+            registry.default_chain(),
+            &receipt,
+            AddressInfo::new(Address::zero(), None),
+            None,
+        );
+
+        assert_eq!(tx.status, TxStatus::Reverted);
+        assert_eq!(tx.block_number, Some(42));
+        assert!(
+            tx.explorer_url
+                .unwrap()
+                .starts_with("https://etherscan.io/tx/0xabab")
+        );
+    }
+}
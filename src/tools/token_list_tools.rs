@@ -0,0 +1,205 @@
+//! Token symbol resolution and search, backed by the token lists of the chains.
+//!
+//! Token inputs of every tool accept a symbol of the selected chain's token list (e.g. `USDC`)
+//! besides an address or ENS name, and `eth` for the native currency where it can be swapped.
+//! A symbol shared by several tokens of the list is rejected as ambiguous, listing the candidates
+//! so the agent can pass the address of the one it means.
+//!
+//! This module:
+//! - Resolves token inputs for the other tool modules.
+//! - Implements [`TokenListTools`] to search a chain's token list by symbol or name.
+use anyhow::Result;
+use ethers::prelude::Address;
+use ethers::utils::to_checksum;
+use rmcp::schemars;
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
+use crate::common::tokens::TokenInfo;
+use crate::tools::MultiTool;
+use crate::tools::ens::AddressInput;
+use crate::tools::traits::TokenListTools;
+
+/// Number of tokens returned by a search when no limit is given
+const DEFAULT_SEARCH_LIMIT: u32 = 10;
+/// Highest number of tokens a search may return
+const MAX_SEARCH_LIMIT: u32 = 50;
+
+/// Token search input struct
+///
+///     Fields:
+///         query (String): Symbol or part of the symbol or name of the tokens to find
+///         limit (Option<u32>): Maximum number of tokens returned, defaults to 10
+///         chain (Option<String>): The chain whose token list is searched, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct TokenSearchInput {
+    #[schemars(description = "Symbol, or part of the symbol or name, of the tokens to find")]
+    pub query: String,
+    #[schemars(description = "Maximum number of tokens returned, 10 by default and at most 50")]
+    pub limit: Option<u32>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// A token of a chain's token list.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct TokenMatch {
+    #[schemars(description = "Checksummed contract address of the token")]
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+}
+
+impl From<&TokenInfo> for TokenMatch {
+    fn from(token: &TokenInfo) -> Self {
+        Self {
+            address: to_checksum(&token.address, None),
+            symbol: token.symbol.clone(),
+            name: token.name.clone(),
+            decimals: token.decimals,
+        }
+    }
+}
+
+impl Display for TokenMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {} decimals): {}",
+            self.symbol, self.name, self.decimals, self.address
+        )
+    }
+}
+
+/// Tokens of a chain's token list matching a search.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct TokenSearchResult {
+    #[schemars(description = "Name of the chain whose token list was searched")]
+    pub chain: String,
+    #[schemars(description = "Name of the token list, if the chain has one")]
+    pub token_list: Option<String>,
+    #[schemars(
+        description = "Matching tokens, exact symbol matches first, then symbols starting with the query, then symbols or names containing it"
+    )]
+    pub tokens: Vec<TokenMatch>,
+}
+
+impl Display for TokenSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(list) = &self.token_list else {
+            return write!(f, "No token list configured for chain {}", self.chain);
+        };
+        write!(
+            f,
+            "{} tokens found in {list} on {}",
+            self.tokens.len(),
+            self.chain
+        )?;
+        for token in &self.tokens {
+            write!(f, "\n- {token}")?;
+        }
+        Ok(())
+    }
+}
+
+impl MultiTool {
+    /// Resolves an ERC20 token input: a symbol of the chain's token list, an address or an ENS
+    /// name. Symbols are looked up first, so that symbols with a dot (e.g. `USDC.e`) aren't taken
+    /// for ENS names.
+    pub(crate) async fn resolve_token_address(
+        &self,
+        chain: &Chain,
+        input: &str,
+    ) -> Result<Address> {
+        let symbol = input.trim();
+        match chain.config.tokens.by_symbol(symbol).as_slice() {
+            [token] => Ok(token.address),
+            [] => match AddressInput::parse(input) {
+                Ok(_) => self.resolve_address(chain, input).await,
+                Err(_) if chain.config.tokens.is_empty() => Err(ToolError::invalid_params(
+                    "token",
+                    format!(
+                        "{symbol} is neither an address nor an ENS name, and chain {} has no token list to look symbols up",
+                        chain.config.name
+                    ),
+                )
+                .into()),
+                Err(_) => Err(ToolError::NotFound {
+                    what: String::from("token symbol"),
+                    name: symbol.to_string(),
+                    detail: Some(format!(
+                        "not in the token list of chain {}, search it with search_tokens or pass the token address",
+                        chain.config.name
+                    )),
+                }
+                .into()),
+            },
+            tokens => {
+                let candidates: Vec<String> = tokens
+                    .iter()
+                    .map(|token| format!("{} ({})", to_checksum(&token.address, None), token.name))
+                    .collect();
+                Err(ToolError::invalid_params(
+                    "token",
+                    format!(
+                        "{symbol} is ambiguous on chain {}, pass the address of one of: {}",
+                        chain.config.name,
+                        candidates.join(", ")
+                    ),
+                )
+                .into())
+            }
+        }
+    }
+
+    /// Resolves a token input, `None` for the native currency given as `eth`
+    pub(crate) async fn resolve_token(
+        &self,
+        chain: &Chain,
+        input: &str,
+    ) -> Result<Option<Address>> {
+        if input.trim().eq_ignore_ascii_case("eth") {
+            return Ok(None);
+        }
+        Ok(Some(self.resolve_token_address(chain, input).await?))
+    }
+}
+
+/// TokenListTools
+///
+///     Description:
+///         Searches the token lists loaded for the configured chains.
+///
+impl TokenListTools for MultiTool {
+    async fn search_tokens(&self, input: TokenSearchInput) -> Result<TokenSearchResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        if input.query.trim().is_empty() {
+            return Err(ToolError::invalid_params("query", "must not be empty").into());
+        }
+        let limit = input.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+            return Err(ToolError::invalid_params(
+                "limit",
+                format!("must be between 1 and {MAX_SEARCH_LIMIT}"),
+            )
+            .into());
+        }
+
+        let tokens = &chain.config.tokens;
+        Ok(TokenSearchResult {
+            chain: chain.config.name.clone(),
+            token_list: tokens.name.clone(),
+            tokens: tokens
+                .search(&input.query, limit as usize)
+                .into_iter()
+                .map(TokenMatch::from)
+                .collect(),
+        })
+    }
+}
@@ -18,25 +18,54 @@
 //! - Balance queries for native tokens and ERC20 tokens
 //! - Transaction sending capabilities
 //! - Smart contract information retrieval
+//! - Listing of the configured chains, every call can select one by name or chain id
+//!
+//! ### `Erc20Tools`
+//! ERC20 token interface providing:
+//! - Token transfers signed by managed accounts
+//! - Exact amount approvals, allowance queries and revocations
 //!
 //! ### `BraveTools`
 //! Web search interface providing:
 //! - Search query execution via Brave Search API
-//! - Returns formatted search results as strings
+//! - Returns the addresses found in the search results, with their sources and on-chain code and
+//!   token metadata
+//! - General search returning the web, news and discussion results themselves
 //!
 //! ### `ZeroXTools`
 //! DEX aggregation interface providing:
 //! - Token swap quote generation via 0x Protocol API
 //! - Price discovery and routing optimization
+//! - Swap execution from firm quotes, signing Permit2 messages with managed accounts
 //!
 //! ### `UniSwapTools`
 //! Uniswap protocol interface providing:
 //! - Direct ETH ↔ Token swaps via Uniswap contracts
+//! - Multi-hop Token ↔ Token swaps and exact output swaps
 //! - Balance validation for swap operations
+//! - On-chain quotes from the router and pair reserves
 //! - On-chain transaction execution
 //!
+//! ### `UniSwapV3Tools`
+//! Uniswap V3 interface providing:
+//! - Exact input and exact output swaps through the SwapRouter, single or multi-hop
+//! - On-chain quotes from QuoterV2 with fee tier discovery
+//!
+//! ### `TokenListTools`
+//! Token list interface providing:
+//! - Search of a chain's token list by symbol or name
+//!
+//! ### `ResourceTools`
+//! Read-only context exposed as MCP resources:
+//! - Managed accounts and their derivation paths
+//! - Latest block of a chain
+//! - ERC20 token metadata
+//!
 //! ## Usage Pattern
 //!
+//! Methods return the typed results defined next to the tool implementations (shared ones live in
+//! `results`), which the server sends as structured content along with a text summary.
+//!
 //! These traits are typically implemented by the corresponding tool modules and used
 //! by the Agent through dependency injection or trait objects, enabling clean separation
 //! of concerns and testability.
@@ -45,32 +74,94 @@
 //! The separation of these Tools allows tool modules to selectively mock a particular
 //! toolset in order to test the agent's response.
 //! ```
-use crate::tools::uniswap_tools::{SwapEthInput, SwapTokenInput};
-use crate::tools::zero_x_tools::QuoteInput;
+use crate::common::chains::Chain;
+use crate::tools::brave_tools::{AddressSearchResult, GeneralSearchInput, GeneralSearchResult};
+use crate::tools::erc20_tools::{
+    AllowanceInput, AllowanceResult, ApproveInput, TokenTransferInput, TokenTxResult,
+};
+use crate::tools::eth_tools::{ChainList, ContractResult};
+use crate::tools::resources::{ChainHead, ManagedAccounts, TokenMetadata};
+use crate::tools::results::{BalanceResult, SwapResult, TxResult};
+use crate::tools::token_list_tools::{TokenSearchInput, TokenSearchResult};
+use crate::tools::uniswap_tools::{
+    SwapEthInput, SwapExactOutInput, SwapTokenInput, SwapTokensInput, UniswapQuoteInput,
+    UniswapQuoteResult,
+};
+use crate::tools::uniswap_v3_tools::{
+    UniswapV3QuoteInput, UniswapV3QuoteResult, UniswapV3SwapInput,
+};
+use crate::tools::units::AmountUnit;
+use crate::tools::zero_x_tools::{QuoteInput, QuoteResult, ZeroXSwapInput, ZeroXSwapResult};
 use anyhow::Result;
 use ethers::prelude::U256;
 
 /// Interface to evm related tools used by Agent.
 pub(crate) trait EvmTools {
-    async fn get_balance(&self, address: String) -> Result<String>;
-    async fn send(&self, from: String, to: String, amount: String) -> Result<String>;
-    async fn get_contract(&self, contract: String) -> Result<String>;
-    async fn get_erc20_balance(&self, contract: String, account: String) -> Result<String>;
+    async fn get_balance(&self, address: String, chain: Option<String>) -> Result<BalanceResult>;
+    async fn send(
+        &self,
+        from: String,
+        to: String,
+        amount: String,
+        unit: Option<AmountUnit>,
+        chain: Option<String>,
+    ) -> Result<TxResult>;
+    async fn get_contract(&self, contract: String, chain: Option<String>)
+    -> Result<ContractResult>;
+    async fn get_erc20_balance(
+        &self,
+        contract: String,
+        account: String,
+        chain: Option<String>,
+    ) -> Result<BalanceResult>;
+    async fn list_chains(&self) -> Result<ChainList>;
 }
 
 /// Interface to brave related tools used by Agent.
 pub(crate) trait BraveTools {
-    async fn search(&self, query: String) -> Result<String>;
+    async fn search(&self, query: String, chain: Option<String>) -> Result<AddressSearchResult>;
+    async fn general_search(&self, input: GeneralSearchInput) -> Result<GeneralSearchResult>;
+}
+
+/// Interface to ERC20 token transfers and approvals.
+pub(crate) trait Erc20Tools {
+    async fn transfer(&self, input: TokenTransferInput) -> Result<TokenTxResult>;
+    async fn approve(&self, input: ApproveInput) -> Result<TokenTxResult>;
+    async fn allowance(&self, input: AllowanceInput) -> Result<AllowanceResult>;
+    async fn revoke(&self, input: AllowanceInput) -> Result<TokenTxResult>;
 }
 
-/// Interface to 0x api for swap quotes.
+/// Interface to 0x api for swap quotes and swaps.
 pub(crate) trait ZeroXTools {
-    async fn get_quote(&self, input: QuoteInput) -> Result<String>;
+    async fn get_quote(&self, input: QuoteInput) -> Result<QuoteResult>;
+    async fn swap(&self, input: ZeroXSwapInput) -> Result<ZeroXSwapResult>;
 }
 
 /// Interface to Uniswap contract abi.
 pub(crate) trait UniSwapTools {
-    async fn swap_eth_to_token(&self, swap_input: SwapEthInput) -> Result<String>;
-    async fn swap_token_to_eth(&self, swap_input: SwapTokenInput) -> Result<String>;
-    async fn check_balance(&self, amount_in: U256, balance: String) -> Result<()>;
+    async fn swap_eth_to_token(&self, swap_input: SwapEthInput) -> Result<SwapResult>;
+    async fn swap_token_to_eth(&self, swap_input: SwapTokenInput) -> Result<SwapResult>;
+    async fn swap_tokens_to_tokens(&self, swap_input: SwapTokensInput) -> Result<SwapResult>;
+    async fn swap_exact_output(&self, swap_input: SwapExactOutInput) -> Result<SwapResult>;
+    async fn quote(&self, input: UniswapQuoteInput) -> Result<UniswapQuoteResult>;
+    async fn check_balance(&self, chain: &Chain, amount_in: U256, balance: U256) -> Result<()>;
+}
+
+/// Interface to the Uniswap V3 SwapRouter and QuoterV2 contracts.
+pub(crate) trait UniSwapV3Tools {
+    async fn swap_v3(&self, input: UniswapV3SwapInput) -> Result<SwapResult>;
+    async fn quote_v3(&self, input: UniswapV3QuoteInput) -> Result<UniswapV3QuoteResult>;
+}
+
+/// Interface to the token lists of the configured chains.
+pub(crate) trait TokenListTools {
+    async fn search_tokens(&self, input: TokenSearchInput) -> Result<TokenSearchResult>;
+}
+
+/// Interface to the read-only context served as MCP resources.
+pub(crate) trait ResourceTools {
+    async fn managed_accounts(&self) -> Result<ManagedAccounts>;
+    async fn chain_head(&self, chain: Option<String>) -> Result<ChainHead>;
+    async fn token_metadata(&self, address: String, chain: Option<String>)
+    -> Result<TokenMetadata>;
 }
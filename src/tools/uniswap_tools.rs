@@ -7,26 +7,41 @@
 //! # Features
 //!
 //! - **ETH ⟷ ERC20 swaps**: Seamless conversion between ETH and any ERC20 token
-//! - **Slippage protection**: Automatic minimum output calculation with 10% safety margin
+//! - **Token ⟷ token swaps**: Multi-hop paths through given tokens, or through WETH when the
+//!   tokens have no pair of their own
+//! - **Exact output swaps**: Buy an exact amount, selling at most a maximum input
+//! - **Slippage protection**: Minimum output lowered by a slippage tolerance, configurable per swap
+//!   and capped server-wide
 //! - **Balance validation**: Pre-transaction checks to prevent insufficient fund failures
 //! - **Gas estimation**: Accounts for transaction costs in balance calculations
+//! - **On-chain quotes**: Swaps without a `min_amount_out` quote the router themselves, so they
+//!   don't depend on the 0x API
 //!
 //! # Key Functions
 //!
 //! - [`swap_eth_to_token`]: Convert ETH to ERC20 tokens using exact input amounts
-//! - [`swap_token_to_eth`]: Convert ERC20 tokens to ETH (requires prior token approval)
+//! - [`swap_token_to_eth`]: Convert ERC20 tokens to ETH, approving the router when needed
+//! - [`swap_tokens_to_tokens`]: Convert ERC20 tokens to other ERC20 tokens using exact input amounts
+//! - [`swap_exact_output`]: Buy an exact amount of ETH or tokens, bounded by a maximum input
 //! - [`check_balance`]: Validate account has sufficient funds including gas costs
+//! - [`quote`]: Quote a swap on-chain with the router's `getAmountsOut`/`getAmountsIn`, along
+//!   with the pair reserves and price impact
 //!
 //! # Usage
 //!
 //! ```rust
 //! // ETH to Token swap
 //! let eth_input = SwapEthInput {
-//!     uniswap_address: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
+//!     uniswap_address: None, // Uses the configured router
 //!     amount_in: "1.0".to_string(), // 1 ETH
+//!     amount_in_unit: None, // ether
 //!     min_amount_out: "1000000000000000000".to_string(), // Expected tokens in base units
+//!     min_amount_out_unit: Some(AmountUnit::Wei),
+//!     slippage_bps: Some(100), // 1%, defaults to the configured slippage
+//!     deadline_secs: None, // Uses the configured deadline
 //!     to_token_addr: "0xA0b86a33E6441...".to_string(),
 //!     account_addr: "0x742d35Cc6aF4...".to_string(),
+//!     chain: None, // Uses the default chain
 //! };
 //!
 //! multi_tool.swap_eth_to_token(eth_input).await?;
@@ -34,16 +49,37 @@
 //!
 //! # Important Notes
 //!
-//! - Token-to-ETH swaps require prior approval of the Uniswap Router to spend tokens
-//! - All amounts are automatically adjusted for 10% slippage tolerance
-//! - Transactions include 5-minute deadline for execution
+//! - Swaps selling a token approve the Uniswap Router for the exact amount sold (the maximum input
+//!   for exact output swaps) when its allowance is short, the approval hash is reported along
+//!   with the swap. Only the chain's configured router is approved, a router given in the input
+//!   must already be allowed to spend the tokens sold
+//! - The minimum output is lowered by `slippage_bps` (basis points, 50 = 0.5%), defaulting to
+//!   `uniswap.slippage_bps` and rejected above `uniswap.max_slippage_bps`. Exact output swaps
+//!   without a `max_amount_in` raise the quoted input by the same tolerance
+//! - Exact output swaps of ETH send the maximum input, the router refunds what isn't spent
+//! - Every amount takes an explicit unit (see [`crate::tools::units`]), the defaults are ether for
+//!   ETH amounts and base units for token amounts
+//! - Transactions expire after `deadline_secs`, defaulting to `uniswap.deadline_secs` (5 minutes)
 //! - WETH conversion is handled automatically by the router contract
-use crate::tools::MultiTool;
-use crate::tools::traits::{EvmTools, UniSwapTools};
+//! - Swaps are signed by the managed wallet of `account_addr`, accounts the server holds no key
+//!   for are rejected
+//! - The WETH and default router addresses come from the selected chain in the chain registry
+use crate::common::chains::Chain;
+use crate::common::context::MAX_BPS;
+use crate::common::error::ToolError;
+use crate::tools::erc20_tools::AllowanceUpdate;
+use crate::tools::eth_tools::ERC20;
+use crate::tools::results::{AddressInfo, Amount, SwapResult, TxResult};
+use crate::tools::traits::UniSwapTools;
+use crate::tools::units::AmountUnit;
+use crate::tools::{MultiTool, SignerClient};
+use ethers::abi::Detokenize;
 use ethers::prelude::*;
-use ethers::utils::parse_ether;
+use ethers::utils::to_checksum;
 use rmcp::schemars;
-use std::str::FromStr;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 // Uniswap V2 Router contract interface generated from the ABI.
@@ -58,9 +94,14 @@ use std::time::{SystemTime, UNIX_EPOCH};
 // - `swapTokensForExactETH`: Swap tokens for exact amount of ETH
 // - `swapExactTokensForTokens`: Swap exact amount of tokens for as many other tokens as possible
 // - `swapTokensForExactTokens`: Swap tokens for exact amount of other tokens
+// - `getAmountsOut` / `getAmountsIn`: On-chain quotes along a path of pairs
+// - `factory`: The factory the pairs are looked up in
 abigen!(
     UniswapV2Router,
     r#"[
+        function factory() external view returns (address)
+        function getAmountsOut(uint amountIn, address[] path) external view returns (uint[] memory amounts)
+        function getAmountsIn(uint amountOut, address[] path) external view returns (uint[] memory amounts)
         function swapExactETHForTokens(uint amountOutMin, address[] path, address to, uint deadline) payable returns (uint[] memory amounts)
         function swapETHForExactTokens(uint amountOut, address[] path, address to, uint deadline) payable returns (uint[] memory amounts)
         function swapExactTokensForETH(uint amountIn, uint amountOutMin, address[] path, address to, uint deadline) returns (uint[] memory amounts)
@@ -70,31 +111,74 @@ abigen!(
     ]"#
 );
 
+// Uniswap V2 factory and pair interfaces, used to read the reserves a quote is based on.
+abigen!(
+    UniswapV2Factory,
+    r#"[
+        function getPair(address tokenA, address tokenB) external view returns (address pair)
+    ]"#
+);
+
+abigen!(
+    UniswapV2Pair,
+    r#"[
+        function token0() external view returns (address)
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+    ]"#
+);
+
 /// Input parameters for swapping ETH to ERC20 tokens on Uniswap V2.
 ///
 /// This struct contains all the necessary parameters to execute an ETH-to-token swap
 /// transaction through the Uniswap V2 Router contract.
 ///
 /// # Fields
-/// - `uniswap_address`: The contract address of the Uniswap V2 Router (0x7a250d5630b4cf539739df2c5dacb4c659f2488d on mainnet)
-/// - `min_amount_out`: Minimum tokens expected to receive (slippage protection, in token's base units)
-/// - `amount_in`: Amount of ETH to swap (in ETH units, not wei - will be converted internally)
+/// - `uniswap_address`: The contract address of the Uniswap V2 Router, defaults to the chain's router (0x7a250d5630b4cf539739df2c5dacb4c659f2488d on mainnet)
+/// - `min_amount_out`: Expected tokens to receive before slippage, quoted on-chain when omitted
+/// - `min_amount_out_unit`: Unit of `min_amount_out`, defaults to `wei` (the token's base units)
+/// - `amount_in`: Amount of ETH to swap
+/// - `amount_in_unit`: Unit of `amount_in`, defaults to `ether`
 /// - `to_token_addr`: Contract address of the ERC20 token to receive
-/// - `account_addr`: Ethereum address that will receive the tokens and pay for the transaction
+/// - `account_addr`: Managed account that signs, pays for the transaction and receives the tokens
+/// - `slippage_bps`: Slippage tolerance applied to `min_amount_out`, in basis points
+/// - `deadline_secs`: Seconds the swap stays valid for once sent
+/// - `chain`: Chain to swap on, defaults to the default chain
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct SwapEthInput {
-    #[schemars(description = "Uniswap V2 router contract address")]
-    pub uniswap_address: String,
+    #[schemars(description = "Uniswap V2 router contract address, defaults to the chain's router")]
+    pub uniswap_address: Option<String>,
+    #[schemars(
+        description = "The expected amount of tokens to be swapped for based on rate, in `min_amount_out_unit`, slippage is applied to it. Quoted on-chain when omitted"
+    )]
+    pub min_amount_out: Option<String>,
+    #[schemars(
+        description = "Unit of `min_amount_out`: wei (token base units) or token, defaults to wei"
+    )]
+    pub min_amount_out_unit: Option<AmountUnit>,
     #[schemars(
-        description = "The minimum amount of expected tokens to be swapped for based on rate"
+        description = "The amount of ETH to be swapped from the sender, in `amount_in_unit`"
     )]
-    pub min_amount_out: String,
-    #[schemars(description = "The amount of tokens to be swapped from the sender in ETH not wei")]
     pub amount_in: String,
-    #[schemars(description = "The output token address or contract")]
+    #[schemars(description = "Unit of `amount_in`: wei, gwei, ether or token, defaults to ether")]
+    pub amount_in_unit: Option<AmountUnit>,
+    #[schemars(description = "The output token address, contract, ENS name or token list symbol")]
     pub to_token_addr: String,
-    #[schemars(description = "The address where funds will be swapped from")]
+    #[schemars(
+        description = "The managed account address or ENS name where funds will be swapped from, signing the swap"
+    )]
     pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance applied to `min_amount_out` in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Seconds the swap stays valid for once sent, defaults to the server's setting"
+    )]
+    pub deadline_secs: Option<u64>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
 }
 
 /// Input parameters for swapping ERC20 tokens to ETH on Uniswap V2.
@@ -102,27 +186,798 @@ pub struct SwapEthInput {
 /// This struct contains all the necessary parameters to execute a token-to-ETH swap
 /// transaction through the Uniswap V2 Router contract.
 ///
-/// **Important**: Before executing this swap, the token contract must have approved
-/// the Uniswap Router to spend the specified amount of tokens.
+/// The router is approved for the exact amount sold when its allowance falls short, so no prior
+/// approval is needed.
 ///
 /// # Fields
-/// - `uniswap_address`: The contract address of the Uniswap V2 Router
-/// - `amount_in`: Amount of tokens to swap (in token's base units - e.g., for USDC with 6 decimals, use "1000000" for 1 USDC)
-/// - `min_amount_out`: Minimum ETH expected to receive (in ETH units, will be converted to wei internally)
+/// - `uniswap_address`: The contract address of the Uniswap V2 Router, defaults to the chain's router
+/// - `amount_in`: Amount of tokens to swap
+/// - `amount_in_unit`: Unit of `amount_in`, defaults to `wei` (the token's base units - e.g., for
+///   USDC with 6 decimals, use "1000000" or "1" with `token` for 1 USDC)
+/// - `min_amount_out`: ETH expected to receive before slippage, quoted on-chain when omitted
+/// - `min_amount_out_unit`: Unit of `min_amount_out`, defaults to `ether`
 /// - `from_token_addr`: Contract address of the ERC20 token being swapped
-/// - `account_addr`: Ethereum address that owns the tokens and will receive the ETH
+/// - `account_addr`: Managed account that signs, owns the tokens and will receive the ETH
+/// - `slippage_bps`: Slippage tolerance applied to `min_amount_out`, in basis points
+/// - `deadline_secs`: Seconds the swap stays valid for once sent
+/// - `chain`: Chain to swap on, defaults to the default chain
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct SwapTokenInput {
-    #[schemars(description = "Uniswap V2 router contract address")]
-    pub uniswap_address: String,
-    #[schemars(description = "The amount of tokens expected to sell for eth")]
+    #[schemars(
+        description = "Uniswap V2 router contract address, defaults to the chain's router. Only the chain's router is approved automatically, another router must already be allowed to spend the tokens sold"
+    )]
+    pub uniswap_address: Option<String>,
+    #[schemars(description = "The amount of tokens expected to sell for eth, in `amount_in_unit`")]
     pub amount_in: String,
-    #[schemars(description = "The minimum amount of eth expected to receive in ETH")]
-    pub min_amount_out: String,
-    #[schemars(description = "The input token address or contract being swapped for ETH")]
+    #[schemars(
+        description = "Unit of `amount_in`: wei (token base units) or token, defaults to wei"
+    )]
+    pub amount_in_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The amount of eth expected to receive, in `min_amount_out_unit`, slippage is applied to it. Quoted on-chain when omitted"
+    )]
+    pub min_amount_out: Option<String>,
+    #[schemars(
+        description = "Unit of `min_amount_out`: wei, gwei, ether or token, defaults to ether"
+    )]
+    pub min_amount_out_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The input token address, contract, ENS name or token list symbol being swapped for ETH"
+    )]
+    pub from_token_addr: String,
+    #[schemars(
+        description = "The managed account address or ENS name where funds will be swapped from, signing the swap"
+    )]
+    pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance applied to `min_amount_out` in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Seconds the swap stays valid for once sent, defaults to the server's setting"
+    )]
+    pub deadline_secs: Option<u64>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Input parameters for swapping ERC20 tokens to other ERC20 tokens on Uniswap V2.
+///
+/// The swap goes through the `via` tokens when given. Otherwise it uses the tokens' own pair, or
+/// goes through WETH when they have none (or always, with `route_via_weth`). The router is
+/// approved for the exact amount sold when its allowance falls short.
+///
+/// # Fields
+/// - `uniswap_address`: The contract address of the Uniswap V2 Router, defaults to the chain's router
+/// - `from_token_addr`: Contract address of the ERC20 token being sold
+/// - `to_token_addr`: Contract address of the ERC20 token to receive
+/// - `via`: Intermediate tokens of a multi-hop path, in order
+/// - `route_via_weth`: Whether to route through WETH when `via` is omitted, defaults to only when
+///   the tokens have no pair
+/// - `amount_in`: Amount of tokens to swap
+/// - `amount_in_unit`: Unit of `amount_in`, defaults to `wei` (the sold token's base units)
+/// - `min_amount_out`: Tokens expected to receive before slippage, quoted on-chain when omitted
+/// - `min_amount_out_unit`: Unit of `min_amount_out`, defaults to `wei` (the bought token's base units)
+/// - `account_addr`: Managed account that signs, owns the tokens and will receive the output
+/// - `slippage_bps`: Slippage tolerance applied to `min_amount_out`, in basis points
+/// - `deadline_secs`: Seconds the swap stays valid for once sent
+/// - `chain`: Chain to swap on, defaults to the default chain
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SwapTokensInput {
+    #[schemars(
+        description = "Uniswap V2 router contract address, defaults to the chain's router. Only the chain's router is approved automatically, another router must already be allowed to spend the tokens sold"
+    )]
+    pub uniswap_address: Option<String>,
+    #[schemars(
+        description = "The input token address, contract, ENS name or token list symbol being sold"
+    )]
     pub from_token_addr: String,
-    #[schemars(description = "The address where funds will be swapped from")]
+    #[schemars(description = "The output token address, contract, ENS name or token list symbol")]
+    pub to_token_addr: String,
+    #[schemars(
+        description = "Intermediate token addresses, ENS names or token list symbols the swap goes through, in order, for multi-hop swaps"
+    )]
+    pub via: Option<Vec<String>>,
+    #[schemars(
+        description = "Route through WETH when `via` is omitted, defaults to only when the tokens have no pair of their own"
+    )]
+    pub route_via_weth: Option<bool>,
+    #[schemars(description = "The amount of tokens sold, in `amount_in_unit`")]
+    pub amount_in: String,
+    #[schemars(
+        description = "Unit of `amount_in`: wei (token base units) or token, defaults to wei"
+    )]
+    pub amount_in_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The expected amount of output tokens, in `min_amount_out_unit`, slippage is applied to it. Quoted on-chain when omitted"
+    )]
+    pub min_amount_out: Option<String>,
+    #[schemars(
+        description = "Unit of `min_amount_out`: wei (token base units) or token, defaults to wei"
+    )]
+    pub min_amount_out_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The managed account address or ENS name where funds will be swapped from, signing the swap"
+    )]
+    pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance applied to `min_amount_out` in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Seconds the swap stays valid for once sent, defaults to the server's setting"
+    )]
+    pub deadline_secs: Option<u64>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Input parameters for buying an exact amount of ETH or tokens on Uniswap V2.
+///
+/// Uses `swapETHForExactTokens`, `swapTokensForExactETH` or `swapTokensForExactTokens` depending
+/// on the tokens. The swap sells at most `max_amount_in`, which is quoted on-chain and raised by
+/// the slippage tolerance when omitted. Tokens sold are approved up to that maximum.
+///
+/// # Fields
+/// - `uniswap_address`: The contract address of the Uniswap V2 Router, defaults to the chain's router
+/// - `from_token`: Token sold, `eth` or a token address
+/// - `to_token`: Token bought, `eth` or a token address
+/// - `via`: Intermediate tokens of a multi-hop path, in order
+/// - `route_via_weth`: Whether to route token to token swaps through WETH when `via` is omitted
+/// - `amount_out`: Exact amount bought
+/// - `amount_out_unit`: Unit of `amount_out`, defaults to `ether` for ETH and `wei` for tokens
+/// - `max_amount_in`: Most that may be sold, quoted on-chain when omitted
+/// - `max_amount_in_unit`: Unit of `max_amount_in`, defaults to `ether` for ETH and `wei` for tokens
+/// - `account_addr`: Managed account that signs, pays and will receive the output
+/// - `slippage_bps`: Slippage tolerance added to the quoted input, in basis points
+/// - `deadline_secs`: Seconds the swap stays valid for once sent
+/// - `chain`: Chain to swap on, defaults to the default chain
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SwapExactOutInput {
+    #[schemars(
+        description = "Uniswap V2 router contract address, defaults to the chain's router. Only the chain's router is approved automatically, another router must already be allowed to spend the tokens sold"
+    )]
+    pub uniswap_address: Option<String>,
+    #[schemars(
+        description = "The token sold, eth or a token address, ENS name or token list symbol"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "The token bought, eth or a token address, ENS name or token list symbol"
+    )]
+    pub to_token: String,
+    #[schemars(
+        description = "Intermediate token addresses, ENS names or token list symbols the swap goes through, in order, for multi-hop swaps"
+    )]
+    pub via: Option<Vec<String>>,
+    #[schemars(
+        description = "Route token to token swaps through WETH when `via` is omitted, defaults to only when the tokens have no pair of their own"
+    )]
+    pub route_via_weth: Option<bool>,
+    #[schemars(description = "The exact amount bought, in `amount_out_unit`")]
+    pub amount_out: String,
+    #[schemars(
+        description = "Unit of `amount_out`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub amount_out_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The most that may be sold, in `max_amount_in_unit`. Quoted on-chain and raised by the slippage tolerance when omitted"
+    )]
+    pub max_amount_in: Option<String>,
+    #[schemars(
+        description = "Unit of `max_amount_in`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub max_amount_in_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The managed account address or ENS name where funds will be swapped from, signing the swap"
+    )]
     pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance added to the quoted input in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Seconds the swap stays valid for once sent, defaults to the server's setting"
+    )]
+    pub deadline_secs: Option<u64>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Which side of a quote is fixed.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, Serialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteSide {
+    /// `amount` is sold, the output is quoted with `getAmountsOut`
+    #[default]
+    ExactIn,
+    /// `amount` is bought, the input is quoted with `getAmountsIn`
+    ExactOut,
+}
+
+/// Input parameters for an on-chain Uniswap V2 quote.
+///
+/// # Fields
+/// - `uniswap_address`: The contract address of the Uniswap V2 Router, defaults to the chain's router
+/// - `from_token`: Token sold, `eth` or a token address
+/// - `to_token`: Token bought, `eth` or a token address
+/// - `via`: Intermediate tokens of a multi-hop path, in order
+/// - `route_via_weth`: Whether to route token to token quotes through WETH when `via` is omitted
+/// - `amount`: Amount sold for `exact_in` quotes, or bought for `exact_out` quotes
+/// - `amount_unit`: Unit of `amount`, defaults to `ether` for ETH and `wei` for tokens
+/// - `side`: Whether `amount` is sold (`exact_in`, the default) or bought (`exact_out`)
+/// - `chain`: Chain to quote on, defaults to the default chain
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct UniswapQuoteInput {
+    #[schemars(description = "Uniswap V2 router contract address, defaults to the chain's router")]
+    pub uniswap_address: Option<String>,
+    #[schemars(
+        description = "The token sold, eth or a token address, ENS name or token list symbol"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "The token bought, eth or a token address, ENS name or token list symbol"
+    )]
+    pub to_token: String,
+    #[schemars(
+        description = "Intermediate token addresses, ENS names or token list symbols the swap goes through, in order, for multi-hop swaps"
+    )]
+    pub via: Option<Vec<String>>,
+    #[schemars(
+        description = "Route token to token quotes through WETH when `via` is omitted, defaults to only when the tokens have no pair of their own"
+    )]
+    pub route_via_weth: Option<bool>,
+    #[schemars(
+        description = "The amount sold for exact_in quotes or bought for exact_out quotes, in `amount_unit`"
+    )]
+    pub amount: String,
+    #[schemars(
+        description = "Unit of `amount`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(description = "exact_in (default) when `amount` is sold, exact_out when bought")]
+    pub side: Option<QuoteSide>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Reserves of a pair along a quoted path, oriented in the direction of the swap.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct PairReserves {
+    #[schemars(description = "Pair contract")]
+    pub pair: String,
+    #[schemars(description = "Reserve of the token going into the pair")]
+    pub reserve_in: Amount,
+    #[schemars(description = "Reserve of the token coming out of the pair")]
+    pub reserve_out: Amount,
+}
+
+/// On-chain quote of a swap through Uniswap V2 pairs.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct UniswapQuoteResult {
+    #[schemars(description = "Name of the chain the quote is for")]
+    pub chain: String,
+    #[schemars(description = "Router the quote was read from")]
+    pub router: AddressInfo,
+    #[schemars(description = "Whether the input or the output amount was fixed")]
+    pub side: QuoteSide,
+    #[schemars(description = "Tokens the swap goes through, ETH is routed as the wrapped token")]
+    pub path: Vec<String>,
+    #[schemars(description = "Amount sold")]
+    pub amount_in: Amount,
+    #[schemars(description = "Amount bought")]
+    pub amount_out: Amount,
+    #[schemars(description = "Reserves of every pair along the path")]
+    pub pairs: Vec<PairReserves>,
+    #[schemars(
+        description = "Difference between the quote and the pairs' mid price, in basis points, including the 0.3% pool fee"
+    )]
+    pub price_impact_bps: u32,
+}
+
+impl Display for UniswapQuoteResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Uniswap V2 quote on {}: {} for {}, price impact {} bps",
+            self.chain, self.amount_in, self.amount_out, self.price_impact_bps
+        )
+    }
+}
+
+/// Lowers an expected output by a slippage tolerance in basis points, `field` names the amount
+/// in the error when it is too large to be scaled
+pub(crate) fn apply_slippage(
+    field: &str,
+    expected: U256,
+    slippage_bps: u32,
+) -> Result<U256, ToolError> {
+    expected
+        .checked_mul(U256::from(MAX_BPS - slippage_bps))
+        .map(|scaled| scaled / U256::from(MAX_BPS))
+        .ok_or_else(|| slippage_overflow(field))
+}
+
+/// Raises an expected input by a slippage tolerance in basis points, rounding up
+pub(crate) fn raise_by_slippage(
+    field: &str,
+    expected: U256,
+    slippage_bps: u32,
+) -> Result<U256, ToolError> {
+    let max_bps = U256::from(MAX_BPS);
+    expected
+        .checked_mul(U256::from(MAX_BPS + slippage_bps))
+        .and_then(|scaled| scaled.checked_add(max_bps - 1))
+        .map(|scaled| scaled / max_bps)
+        .ok_or_else(|| slippage_overflow(field))
+}
+
+fn slippage_overflow(field: &str) -> ToolError {
+    ToolError::invalid_params(field, "too large to apply the slippage tolerance to")
+}
+
+/// Deadline (unix timestamp) of a swap sent now and valid for `deadline_secs`
+pub(crate) fn swap_deadline(deadline_secs: u64) -> anyhow::Result<u64> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    now.checked_add(deadline_secs).ok_or_else(|| {
+        ToolError::invalid_params("deadline_secs", format!("{deadline_secs} is too large")).into()
+    })
+}
+
+/// Unit of an amount when none is given: ether for ETH (`None`), base units for tokens
+pub(crate) fn default_unit(token: Option<Address>) -> AmountUnit {
+    match token {
+        Some(_) => AmountUnit::Wei,
+        None => AmountUnit::Ether,
+    }
+}
+
+/// Price impact of a quote in basis points: how much less the path returns than at the pairs' mid
+/// price, fee included. `reserves` holds the (in, out) reserves of every pair along the path.
+fn price_impact_bps(amount_in: U256, amount_out: U256, reserves: &[(U256, U256)]) -> u32 {
+    // Lossy is fine, the impact is an indication and doesn't move funds
+    let float = |value: U256| value.to_string().parse::<f64>().unwrap_or_default();
+    let mid_out = reserves
+        .iter()
+        .fold(float(amount_in), |amount, (reserve_in, reserve_out)| {
+            amount * float(*reserve_out) / float(*reserve_in)
+        });
+    if mid_out <= 0.0 {
+        return 0;
+    }
+    let impact = (1.0 - float(amount_out) / mid_out) * f64::from(MAX_BPS);
+    impact.clamp(0.0, f64::from(MAX_BPS)).round() as u32
+}
+
+/// A swap ready to be sent, with its amounts bounded by the slippage tolerance
+pub(crate) struct SwapPlan {
+    pub(crate) side: QuoteSide,
+    pub(crate) path: Vec<Address>,
+    /// Fee tier of every pool along `path`, for Uniswap V3 swaps
+    pub(crate) fees: Option<Vec<u32>>,
+    /// Whether ETH is sold or bought, routed as WETH at that end of `path`
+    pub(crate) eth_in: bool,
+    pub(crate) eth_out: bool,
+    /// Exact amount sold, or the maximum sold for exact output swaps
+    pub(crate) amount_in: Amount,
+    /// Minimum amount bought, or the exact amount bought for exact output swaps
+    pub(crate) amount_out: Amount,
+    pub(crate) slippage_bps: u32,
+    /// Seconds the swap stays valid for, the deadline is set when it is sent
+    pub(crate) deadline_secs: u64,
+}
+
+/// Uniswap V2 router call of a planned swap. The function depends on the fixed side and on which
+/// end is ETH, the bounds being the minimum output or the maximum input (slippage protection).
+fn v2_swap_call(
+    router: UniswapV2Router<SignerClient>,
+    plan: &SwapPlan,
+    account: Address,
+    deadline: u64,
+) -> ContractCall<SignerClient, Vec<U256>> {
+    let (value_in, value_out) = (plan.amount_in.value(), plan.amount_out.value());
+    let path = plan.path.clone();
+    let deadline = U256::from(deadline);
+    match (plan.side, plan.eth_in, plan.eth_out) {
+        (QuoteSide::ExactIn, true, _) => router
+            .swap_exact_eth_for_tokens(value_out, path, account, deadline)
+            .value(value_in),
+        (QuoteSide::ExactIn, false, true) => {
+            router.swap_exact_tokens_for_eth(value_in, value_out, path, account, deadline)
+        }
+        (QuoteSide::ExactIn, false, false) => {
+            router.swap_exact_tokens_for_tokens(value_in, value_out, path, account, deadline)
+        }
+        // Unspent ETH is refunded by the router
+        (QuoteSide::ExactOut, true, _) => router
+            .swap_eth_for_exact_tokens(value_out, path, account, deadline)
+            .value(value_in),
+        (QuoteSide::ExactOut, false, true) => {
+            router.swap_tokens_for_exact_eth(value_out, value_in, path, account, deadline)
+        }
+        (QuoteSide::ExactOut, false, false) => {
+            router.swap_tokens_for_exact_tokens(value_out, value_in, path, account, deadline)
+        }
+    }
+}
+
+impl MultiTool {
+    /// Slippage tolerance and validity in seconds of a swap, falling back to the configured
+    /// defaults. Slippage above the configured maximum is rejected.
+    pub(crate) fn swap_limits(
+        &self,
+        slippage_bps: Option<u32>,
+        deadline_secs: Option<u64>,
+    ) -> anyhow::Result<(u32, u64)> {
+        let slippage_bps = slippage_bps.unwrap_or(self.uniswap.slippage_bps);
+        if slippage_bps > self.uniswap.max_slippage_bps {
+            return Err(ToolError::invalid_params(
+                "slippage_bps",
+                format!(
+                    "{slippage_bps} exceeds the maximum of {} bps",
+                    self.uniswap.max_slippage_bps
+                ),
+            )
+            .into());
+        }
+
+        let deadline_secs = deadline_secs.unwrap_or(self.uniswap.deadline_secs);
+        if deadline_secs == 0 {
+            return Err(ToolError::invalid_params("deadline_secs", "must be at least 1").into());
+        }
+        // Rejected when planning the swap rather than after its approval
+        swap_deadline(deadline_secs)?;
+        Ok((slippage_bps, deadline_secs))
+    }
+
+    /// Amounts along a path quoted by the router, from the amount sold to the amount bought
+    async fn v2_amounts(
+        &self,
+        chain: &Chain,
+        router: Address,
+        path: &[Address],
+        amount: U256,
+        side: QuoteSide,
+    ) -> anyhow::Result<Vec<U256>> {
+        let router = UniswapV2Router::new(router, chain.provider.clone());
+        let call = match side {
+            QuoteSide::ExactIn => router.get_amounts_out(amount, path.to_vec()),
+            QuoteSide::ExactOut => router.get_amounts_in(amount, path.to_vec()),
+        };
+        call.call().await.map_err(ToolError::from_contract)
+    }
+
+    /// Pair of two tokens in the router's factory, zero when there is none
+    async fn v2_pair(
+        &self,
+        chain: &Chain,
+        router: Address,
+        token_a: Address,
+        token_b: Address,
+    ) -> anyhow::Result<Address> {
+        let factory = UniswapV2Router::new(router, chain.provider.clone())
+            .factory()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        UniswapV2Factory::new(factory, chain.provider.clone())
+            .get_pair(token_a, token_b)
+            .call()
+            .await
+            .map_err(ToolError::from_contract)
+    }
+
+    /// Pair addresses and (in, out) reserves of every hop of a path
+    async fn v2_reserves(
+        &self,
+        chain: &Chain,
+        router: Address,
+        path: &[Address],
+    ) -> anyhow::Result<Vec<(Address, U256, U256)>> {
+        let mut reserves = Vec::with_capacity(path.len().saturating_sub(1));
+        for hop in path.windows(2) {
+            let (token_in, token_out) = (hop[0], hop[1]);
+            let pair = self.v2_pair(chain, router, token_in, token_out).await?;
+            if pair.is_zero() {
+                return Err(ToolError::not_found(
+                    "Uniswap V2 pair",
+                    format!("{token_in:?}/{token_out:?}"),
+                )
+                .into());
+            }
+
+            let pair_contract = UniswapV2Pair::new(pair, chain.provider.clone());
+            let token0 = pair_contract
+                .token_0()
+                .call()
+                .await
+                .map_err(ToolError::from_contract)?;
+            let (reserve0, reserve1, _) = pair_contract
+                .get_reserves()
+                .call()
+                .await
+                .map_err(ToolError::from_contract)?;
+            let (reserve0, reserve1) = (U256::from(reserve0), U256::from(reserve1));
+            if token0 == token_in {
+                reserves.push((pair, reserve0, reserve1));
+            } else {
+                reserves.push((pair, reserve1, reserve0));
+            }
+        }
+        Ok(reserves)
+    }
+
+    /// Path of a swap from `from` to `to` through the `via` tokens, `None` standing for ETH which
+    /// is routed as WETH
+    pub(crate) async fn via_path(
+        &self,
+        chain: &Chain,
+        (from, to): (Option<Address>, Option<Address>),
+        via: &[String],
+    ) -> anyhow::Result<Vec<Address>> {
+        let weth_addr = chain.wrapped_native()?;
+        let (from, to) = (from.unwrap_or(weth_addr), to.unwrap_or(weth_addr));
+        if from == to {
+            return Err(ToolError::invalid_params(
+                "to_token",
+                "the tokens sold and bought must differ",
+            )
+            .into());
+        }
+
+        let mut path = vec![from];
+        for token in via {
+            path.push(self.resolve_token_address(chain, token).await?);
+        }
+        path.push(to);
+
+        if let Some(hop) = path.windows(2).find(|hop| hop[0] == hop[1]) {
+            return Err(ToolError::invalid_params(
+                "via",
+                format!("the path swaps {:?} for itself", hop[0]),
+            )
+            .into());
+        }
+        Ok(path)
+    }
+
+    /// Path of a Uniswap V2 swap from `from` to `to`, `None` standing for ETH.
+    ///
+    /// The path goes through the `via` tokens when given. Otherwise token to token swaps go
+    /// through WETH when `route_via_weth` is set, or when it is unset and the tokens have no pair.
+    async fn swap_path(
+        &self,
+        chain: &Chain,
+        router: Address,
+        tokens: (Option<Address>, Option<Address>),
+        via: Option<&[String]>,
+        route_via_weth: Option<bool>,
+    ) -> anyhow::Result<Vec<Address>> {
+        let via = via.unwrap_or_default();
+        let path = self.via_path(chain, tokens, via).await?;
+        let weth_addr = chain.wrapped_native()?;
+        let (from, to) = (path[0], path[path.len() - 1]);
+        if !via.is_empty() || from == weth_addr || to == weth_addr {
+            return Ok(path);
+        }
+
+        let through_weth = match route_via_weth {
+            Some(through_weth) => through_weth,
+            None => self.v2_pair(chain, router, from, to).await?.is_zero(),
+        };
+        if through_weth {
+            return Ok(vec![from, weth_addr, to]);
+        }
+        Ok(path)
+    }
+
+    /// Counterpart of a swap's fixed amount before slippage: the caller's amount when given,
+    /// otherwise the router's on-chain quote. That's the output for exact input swaps and the
+    /// input for exact output swaps, `token` is its token and `None` for the native currency.
+    #[allow(clippy::too_many_arguments)]
+    async fn expected_amount(
+        &self,
+        chain: &Chain,
+        router: Address,
+        path: &[Address],
+        side: QuoteSide,
+        fixed: U256,
+        (field, given, unit): (&str, Option<&str>, AmountUnit),
+        token: Option<Address>,
+    ) -> anyhow::Result<Amount> {
+        if let Some(given) = given {
+            return self.parse_amount(chain, field, given, unit, token).await;
+        }
+
+        let amounts = self.v2_amounts(chain, router, path, fixed, side).await?;
+        let quoted = match side {
+            QuoteSide::ExactIn => amounts.last(),
+            QuoteSide::ExactOut => amounts.first(),
+        }
+        .copied()
+        .unwrap_or_default();
+        tracing::info!("Quoted {quoted} for {fixed} ({side:?}) on-chain");
+        let (symbol, decimals) = self.denomination(chain, token).await?;
+        Ok(Amount::new(quoted, symbol, decimals))
+    }
+
+    /// Plans an exact input swap, lowering the expected output by the slippage tolerance
+    #[allow(clippy::too_many_arguments)]
+    async fn plan_exact_in(
+        &self,
+        chain: &Chain,
+        router: Address,
+        path: Vec<Address>,
+        (from, to): (Option<Address>, Option<Address>),
+        amount_in: Amount,
+        min_amount_out: (Option<&str>, AmountUnit),
+        limits: (Option<u32>, Option<u64>),
+    ) -> anyhow::Result<SwapPlan> {
+        // Calculate minimum tokens out with slippage (e.g., 50 bps slippage = accept 99.5% of
+        // expected), quoting the expected output on-chain when it isn't given.
+        let (slippage_bps, deadline_secs) = self.swap_limits(limits.0, limits.1)?;
+        let (given, unit) = min_amount_out;
+        let expected_out = self
+            .expected_amount(
+                chain,
+                router,
+                &path,
+                QuoteSide::ExactIn,
+                amount_in.value(),
+                ("min_amount_out", given, unit),
+                to,
+            )
+            .await?;
+        let min_out = apply_slippage("min_amount_out", expected_out.value(), slippage_bps)?;
+        tracing::info!("Min TOKEN EXPECTED: {min_out}");
+
+        Ok(SwapPlan {
+            side: QuoteSide::ExactIn,
+            path,
+            fees: None,
+            eth_in: from.is_none(),
+            eth_out: to.is_none(),
+            amount_in,
+            amount_out: Amount::new(min_out, expected_out.symbol, expected_out.decimals),
+            slippage_bps,
+            deadline_secs,
+        })
+    }
+
+    /// Sends a planned swap from a managed account and waits for it to be mined.
+    ///
+    /// ETH sold is checked against the account's balance, tokens sold are approved for the
+    /// router when its allowance falls short of the (maximum) amount sold. Only the chain's
+    /// configured routers are approved, any other router must already have the allowance.
+    /// `swap_call` then builds the router call from the planned amounts and the deadline, counted
+    /// from that point, with a client signing for the account.
+    pub(crate) async fn execute_swap<D: Detokenize>(
+        &self,
+        chain: &Chain,
+        router: Address,
+        account: Address,
+        plan: SwapPlan,
+        swap_call: impl FnOnce(
+            Arc<SignerClient>,
+            &SwapPlan,
+            u64,
+        ) -> anyhow::Result<ContractCall<SignerClient, D>>,
+    ) -> anyhow::Result<SwapResult> {
+        let wallet = self.managed_wallet(account)?;
+        let value_in = plan.amount_in.value();
+
+        // Serialize transactions from this account so concurrent swaps don't race on the nonce,
+        // and an approval can't be spent by another transaction before the swap
+        let _guard = self.signer_locks.lock(account).await;
+
+        let mut approvals = AllowanceUpdate::default();
+        if plan.eth_in {
+            // Check account balance first
+            let balance = chain
+                .provider
+                .get_balance(account, None)
+                .await
+                .map_err(ToolError::from_rpc)?;
+            self.check_balance(chain, value_in, balance).await?;
+        } else if chain.is_uniswap_router(router) {
+            // Approve the router for the exact amount sold when its allowance falls short
+            approvals = self
+                .ensure_allowance(chain, plan.path[0], account, router, value_in)
+                .await?;
+        } else {
+            // A router given in the input may only spend what it was explicitly allowed to
+            let allowance = ERC20::new(plan.path[0], chain.provider.clone())
+                .allowance(account, router)
+                .call()
+                .await
+                .map_err(ToolError::from_contract)?;
+            if allowance < value_in {
+                return Err(ToolError::invalid_params(
+                    "router",
+                    format!(
+                        "{router:?} isn't a Uniswap router configured for chain {}, only those are approved automatically. Its allowance of {allowance} falls short of the {value_in} sold, approve it with approve_erc20 first or use the chain's router",
+                        chain.config.name
+                    ),
+                )
+                .into());
+            }
+        }
+
+        // The validity starts now, waiting for the lock or an approval doesn't use it up. Sign with
+        // the managed wallet of the account, for the selected chain
+        let deadline = swap_deadline(plan.deadline_secs)?;
+        let tx = swap_call(Arc::new(self.signer(chain, wallet)), &plan, deadline)?;
+
+        // Send transaction and wait for confirmation
+        let pending_tx = tx.send().await.map_err(ToolError::from_contract)?;
+        let receipt = pending_tx
+            .await
+            .map_err(ToolError::from_rpc)?
+            .ok_or_else(|| anyhow::anyhow!("Transaction failed"))?;
+
+        let mut tx = TxResult::new(
+            chain,
+            &receipt,
+            self.address_info(chain, account).await,
+            Some(self.address_info(chain, router).await),
+        );
+        if plan.eth_in {
+            tx = tx.with_value(plan.amount_in.clone());
+        }
+        Ok(SwapResult {
+            tx,
+            approval: approvals.approval,
+            allowance_reset: approvals.reset,
+            side: plan.side,
+            path: plan
+                .path
+                .iter()
+                .map(|token| to_checksum(token, None))
+                .collect(),
+            fees: plan.fees,
+            amount_in: plan.amount_in,
+            min_amount_out: plan.amount_out,
+            slippage_bps: plan.slippage_bps,
+            deadline,
+        })
+    }
+
+    /// Sends a planned swap through a Uniswap V2 router, see [`MultiTool::execute_swap`]
+    async fn execute_v2_swap(
+        &self,
+        chain: &Chain,
+        router: Address,
+        account: Address,
+        plan: SwapPlan,
+    ) -> anyhow::Result<SwapResult> {
+        self.execute_swap(chain, router, account, plan, |signer, plan, deadline| {
+            Ok(v2_swap_call(
+                UniswapV2Router::new(router, signer),
+                plan,
+                account,
+                deadline,
+            ))
+        })
+        .await
+    }
+
+    /// Router address given in the swap input, falling back to the chain's router
+    async fn router_address(&self, chain: &Chain, router: Option<&str>) -> anyhow::Result<Address> {
+        match router {
+            Some(router) => self.resolve_address(chain, router).await,
+            None => chain.uniswap_router(),
+        }
+    }
 }
 
 impl UniSwapTools for MultiTool {
@@ -136,53 +991,46 @@ impl UniSwapTools for MultiTool {
     /// * `input` - SwapEthInput struct containing swap parameters
     ///
     /// # Returns
-    /// * `Result<String>` - Success message with transaction hash and gas used, or error
-    async fn swap_eth_to_token(&self, input: SwapEthInput) -> anyhow::Result<String> {
+    /// * `Result<SwapResult>` - The mined swap transaction with its amounts, or error
+    async fn swap_eth_to_token(&self, input: SwapEthInput) -> anyhow::Result<SwapResult> {
         tracing::info!("Swapping Eth for Token");
-        let token_addr = Address::from_str(&input.to_token_addr)?;
-        let account_addr = Address::from_str(&input.account_addr)?;
-        let weth_addr = Address::from_str(super::WETH_TOKEN_ADDRESS)?;
-        let contract_addr = Address::from_str(&input.uniswap_address)?;
-
-        let eth_amount_in = parse_ether(&input.amount_in)?;
-
-        // Calculate minimum tokens out with slippage (e.g., 5% slippage = accept 95% of expected)
-        // This value might be 0 if the 0x protocol api isn't available.
-        let expected_tokens_out = U256::from_dec_str(&input.min_amount_out)?;
-
-        // Calculate 90% directly (remove 10%) (SLIPPAGE COST) - Trying to allow swaps to go through since this is a test account
-        let min_tokens_out = expected_tokens_out * U256::from(90) / U256::from(100);
-        tracing::info!("Min TOKEN EXPECTED: {min_tokens_out}");
+        let chain = self.chain(input.chain.as_deref())?;
+        let token_addr = self
+            .resolve_token_address(chain, &input.to_token_addr)
+            .await?;
+        let account_addr = self.resolve_address(chain, &input.account_addr).await?;
+        self.managed_wallet(account_addr)?;
+        let weth_addr = chain.wrapped_native()?;
+        let contract_addr = self
+            .router_address(chain, input.uniswap_address.as_deref())
+            .await?;
 
-        let path = vec![weth_addr, token_addr];
-        let deadline = U256::from(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 300);
-
-        // Check account balance first
-        let balance = self.get_balance(input.account_addr).await?;
-        self.check_balance(eth_amount_in, balance).await?;
-
-        let contract = UniswapV2Router::new(contract_addr, self.eth_provider.clone());
-
-        // Build transaction with input values
-        let tx = contract
-            .swap_exact_eth_for_tokens(
-                min_tokens_out, // Minimum tokens to accept (slippage protection)
-                path,
-                account_addr,
-                deadline,
+        let amount_in = self
+            .parse_amount(
+                chain,
+                "amount_in",
+                &input.amount_in,
+                input.amount_in_unit.unwrap_or(AmountUnit::Ether),
+                None,
             )
-            .value(eth_amount_in); // ETH amount to swap
-
-        // Send transaction and wait for confirmation
-        let pending_tx = tx.send().await?;
-        let receipt = pending_tx
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Transaction failed"))?;
+            .await?;
 
-        Ok(format!(
-            "Transaction successful! Hash: {:?}, Gas used: {:?}",
-            receipt.transaction_hash, receipt.gas_used
-        ))
+        let plan = self
+            .plan_exact_in(
+                chain,
+                contract_addr,
+                vec![weth_addr, token_addr],
+                (None, Some(token_addr)),
+                amount_in,
+                (
+                    input.min_amount_out.as_deref(),
+                    input.min_amount_out_unit.unwrap_or(AmountUnit::Wei),
+                ),
+                (input.slippage_bps, input.deadline_secs),
+            )
+            .await?;
+        self.execute_v2_swap(chain, contract_addr, account_addr, plan)
+            .await
     }
     /// Swaps ERC20 tokens for ETH using Uniswap V2.
     ///
@@ -190,56 +1038,271 @@ impl UniSwapTools for MultiTool {
     /// function from the Uniswap V2 Router. The function automatically handles WETH to ETH conversion
     /// internally within the router contract.
     ///
-    /// **IMPORTANT**: Before calling this function, the token contract must have approved the
-    /// Uniswap Router to spend the specified amount of tokens. Without approval, the transaction
-    /// will fail with `TRANSFER_FROM_FAILED` error.
+    /// The router's allowance is checked first, when it is short of the amount sold the exact
+    /// amount is approved and the approval mined before swapping. Both transactions are reported
+    /// in the result.
     ///
     /// # Arguments
     /// * `input` - SwapTokenInput struct containing swap parameters
     ///
     /// # Returns
-    /// * `Result<String>` - Success message with transaction hash and gas used, or error
-    async fn swap_token_to_eth(&self, input: SwapTokenInput) -> anyhow::Result<String> {
+    /// * `Result<SwapResult>` - The mined swap transaction with its amounts, or error
+    async fn swap_token_to_eth(&self, input: SwapTokenInput) -> anyhow::Result<SwapResult> {
         tracing::info!("Swapping Token for ETH");
-        let from_token_addr = Address::from_str(&input.from_token_addr)?;
-        let account_addr = Address::from_str(&input.account_addr)?;
-        let weth_addr = Address::from_str(super::WETH_TOKEN_ADDRESS)?;
-        let contract_addr = Address::from_str(&input.uniswap_address)?;
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token_addr = self
+            .resolve_token_address(chain, &input.from_token_addr)
+            .await?;
+        let account_addr = self.resolve_address(chain, &input.account_addr).await?;
+        self.managed_wallet(account_addr)?;
+        let weth_addr = chain.wrapped_native()?;
+        let contract_addr = self
+            .router_address(chain, input.uniswap_address.as_deref())
+            .await?;
+
+        let amount_in = self
+            .parse_amount(
+                chain,
+                "amount_in",
+                &input.amount_in,
+                input.amount_in_unit.unwrap_or(AmountUnit::Wei),
+                Some(from_token_addr),
+            )
+            .await?;
+
+        let plan = self
+            .plan_exact_in(
+                chain,
+                contract_addr,
+                vec![from_token_addr, weth_addr],
+                (Some(from_token_addr), None),
+                amount_in,
+                (
+                    input.min_amount_out.as_deref(),
+                    input.min_amount_out_unit.unwrap_or(AmountUnit::Ether),
+                ),
+                (input.slippage_bps, input.deadline_secs),
+            )
+            .await?;
+        self.execute_v2_swap(chain, contract_addr, account_addr, plan)
+            .await
+    }
+
+    /// Swaps ERC20 tokens for other ERC20 tokens using Uniswap V2's `swapExactTokensForTokens`.
+    ///
+    /// The path goes through the `via` tokens when given, otherwise through the tokens' own pair
+    /// or WETH (see [`SwapTokensInput`]). The router is approved for the exact amount sold when
+    /// its allowance falls short, as for token to ETH swaps.
+    async fn swap_tokens_to_tokens(&self, input: SwapTokensInput) -> anyhow::Result<SwapResult> {
+        tracing::info!("Swapping Token for Token");
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token_addr = self
+            .resolve_token_address(chain, &input.from_token_addr)
+            .await?;
+        let to_token_addr = self
+            .resolve_token_address(chain, &input.to_token_addr)
+            .await?;
+        let account_addr = self.resolve_address(chain, &input.account_addr).await?;
+        self.managed_wallet(account_addr)?;
+        let contract_addr = self
+            .router_address(chain, input.uniswap_address.as_deref())
+            .await?;
 
-        let token_amount_in = U256::from_dec_str(&input.amount_in)?;
+        let tokens = (Some(from_token_addr), Some(to_token_addr));
+        let path = self
+            .swap_path(
+                chain,
+                contract_addr,
+                tokens,
+                input.via.as_deref(),
+                input.route_via_weth,
+            )
+            .await?;
 
-        // Calculate minimum tokens out with slippage (e.g., 5% slippage = accept 95% of expected)
-        // This value might be 0 if the 0x protocol api isn't available.
-        let expected_tokens_out = parse_ether(&input.min_amount_out)?;
+        let amount_in = self
+            .parse_amount(
+                chain,
+                "amount_in",
+                &input.amount_in,
+                input.amount_in_unit.unwrap_or(AmountUnit::Wei),
+                Some(from_token_addr),
+            )
+            .await?;
 
-        // Calculate 90% directly (remove 10%) (SLIPPAGE COST)
-        let min_tokens_out = expected_tokens_out * U256::from(90) / U256::from(100);
-        tracing::info!("Min TOKEN EXPECTED: {min_tokens_out}");
+        let plan = self
+            .plan_exact_in(
+                chain,
+                contract_addr,
+                path,
+                tokens,
+                amount_in,
+                (
+                    input.min_amount_out.as_deref(),
+                    input.min_amount_out_unit.unwrap_or(AmountUnit::Wei),
+                ),
+                (input.slippage_bps, input.deadline_secs),
+            )
+            .await?;
+        self.execute_v2_swap(chain, contract_addr, account_addr, plan)
+            .await
+    }
 
-        let path = vec![from_token_addr, weth_addr];
-        let deadline = U256::from(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 300);
+    /// Buys an exact amount of ETH or tokens using Uniswap V2's `swapETHForExactTokens`,
+    /// `swapTokensForExactETH` or `swapTokensForExactTokens`.
+    ///
+    /// The input is bounded by `max_amount_in`, or by the router's `getAmountsIn` quote raised by
+    /// the slippage tolerance when it isn't given. Tokens sold are approved up to that bound.
+    async fn swap_exact_output(&self, input: SwapExactOutInput) -> anyhow::Result<SwapResult> {
+        tracing::info!("Swapping for an exact output");
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token = self.resolve_token(chain, &input.from_token).await?;
+        let to_token = self.resolve_token(chain, &input.to_token).await?;
+        let account_addr = self.resolve_address(chain, &input.account_addr).await?;
+        self.managed_wallet(account_addr)?;
+        let contract_addr = self
+            .router_address(chain, input.uniswap_address.as_deref())
+            .await?;
+
+        let path = self
+            .swap_path(
+                chain,
+                contract_addr,
+                (from_token, to_token),
+                input.via.as_deref(),
+                input.route_via_weth,
+            )
+            .await?;
 
-        let contract = UniswapV2Router::new(contract_addr, self.eth_provider.clone());
+        let amount_out = self
+            .parse_amount(
+                chain,
+                "amount_out",
+                &input.amount_out,
+                input.amount_out_unit.unwrap_or(default_unit(to_token)),
+                to_token,
+            )
+            .await?;
 
-        // Build transaction with input values
-        let tx = contract.swap_exact_tokens_for_eth(
-            token_amount_in,
-            min_tokens_out, // Minimum tokens to accept (slippage protection)
+        // A given maximum input is used as is, a quoted one is raised by the slippage tolerance
+        let (slippage_bps, deadline_secs) =
+            self.swap_limits(input.slippage_bps, input.deadline_secs)?;
+        let expected_in = self
+            .expected_amount(
+                chain,
+                contract_addr,
+                &path,
+                QuoteSide::ExactOut,
+                amount_out.value(),
+                (
+                    "max_amount_in",
+                    input.max_amount_in.as_deref(),
+                    input.max_amount_in_unit.unwrap_or(default_unit(from_token)),
+                ),
+                from_token,
+            )
+            .await?;
+        let max_in = match input.max_amount_in {
+            Some(_) => expected_in.value(),
+            None => raise_by_slippage("max_amount_in", expected_in.value(), slippage_bps)?,
+        };
+        tracing::info!("Max TOKEN SOLD: {max_in}");
+
+        let plan = SwapPlan {
+            side: QuoteSide::ExactOut,
             path,
-            account_addr,
-            deadline,
+            fees: None,
+            eth_in: from_token.is_none(),
+            eth_out: to_token.is_none(),
+            amount_in: Amount::new(max_in, expected_in.symbol, expected_in.decimals),
+            amount_out,
+            slippage_bps,
+            deadline_secs,
+        };
+        self.execute_v2_swap(chain, contract_addr, account_addr, plan)
+            .await
+    }
+
+    /// Quotes a swap on-chain with the router's `getAmountsOut` (exact input) or `getAmountsIn`
+    /// (exact output), reading the reserves of the pairs along the path to report the price
+    /// impact. ETH is routed through the chain's wrapped native token, and the path is built as
+    /// for swaps.
+    async fn quote(&self, input: UniswapQuoteInput) -> anyhow::Result<UniswapQuoteResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token = self.resolve_token(chain, &input.from_token).await?;
+        let to_token = self.resolve_token(chain, &input.to_token).await?;
+        let router = self
+            .router_address(chain, input.uniswap_address.as_deref())
+            .await?;
+        let side = input.side.unwrap_or_default();
+
+        let path = self
+            .swap_path(
+                chain,
+                router,
+                (from_token, to_token),
+                input.via.as_deref(),
+                input.route_via_weth,
+            )
+            .await?;
+
+        // The fixed amount is in the sold token for exact input quotes, the bought one otherwise
+        let fixed_token = match side {
+            QuoteSide::ExactIn => from_token,
+            QuoteSide::ExactOut => to_token,
+        };
+        let amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(default_unit(fixed_token)),
+                fixed_token,
+            )
+            .await?;
+
+        let reserves = self.v2_reserves(chain, router, &path).await?;
+        let amounts = self
+            .v2_amounts(chain, router, &path, amount.value(), side)
+            .await?;
+        let amount_in = amounts.first().copied().unwrap_or_default();
+        let amount_out = amounts.last().copied().unwrap_or_default();
+
+        let mut denominations = Vec::with_capacity(path.len());
+        for token in &path {
+            denominations.push(self.denomination(chain, Some(*token)).await?);
+        }
+        let amount_of = |value: U256, (symbol, decimals): &(String, u8)| {
+            Amount::new(value, symbol.clone(), *decimals)
+        };
+        let (native_in, native_out) = (
+            self.denomination(chain, from_token).await?,
+            self.denomination(chain, to_token).await?,
         );
 
-        // Send transaction and wait for confirmation
-        let pending_tx = tx.send().await?;
-        let receipt = pending_tx
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("Transaction failed"))?;
+        let pairs = reserves
+            .iter()
+            .zip(denominations.windows(2))
+            .map(|((pair, reserve_in, reserve_out), hop)| PairReserves {
+                pair: to_checksum(pair, None),
+                reserve_in: amount_of(*reserve_in, &hop[0]),
+                reserve_out: amount_of(*reserve_out, &hop[1]),
+            })
+            .collect();
+        let hop_reserves: Vec<(U256, U256)> = reserves
+            .iter()
+            .map(|(_, reserve_in, reserve_out)| (*reserve_in, *reserve_out))
+            .collect();
 
-        Ok(format!(
-            "Transaction successful! Hash: {:?}, Gas used: {:?}",
-            receipt.transaction_hash, receipt.gas_used
-        ))
+        Ok(UniswapQuoteResult {
+            chain: chain.config.name.clone(),
+            router: self.address_info(chain, router).await,
+            side,
+            path: path.iter().map(|token| to_checksum(token, None)).collect(),
+            amount_in: amount_of(amount_in, &native_in),
+            amount_out: amount_of(amount_out, &native_out),
+            pairs,
+            price_impact_bps: price_impact_bps(amount_in, amount_out, &hop_reserves),
+        })
     }
 
     /// Validates that an account has sufficient balance to cover a transaction amount plus gas fees.
@@ -248,25 +1311,80 @@ impl UniSwapTools for MultiTool {
     /// estimated gas costs. It helps prevent transaction failures due to insufficient funds.
     ///
     /// # Arguments
+    /// * `chain` - The chain whose gas price is used
     /// * `amount_in` - The amount of ETH required for the swap (in wei)
-    /// * `balance` - The current account balance as a string (in wei)
+    /// * `balance` - The current account balance (in wei)
     ///
     /// # Returns
     /// * `Result<()>` - Ok if balance is sufficient, Err with details if insufficient
-    async fn check_balance(&self, amount_in: U256, balance: String) -> anyhow::Result<()> {
-        let bal = U256::from_dec_str(&balance)?;
+    async fn check_balance(
+        &self,
+        chain: &Chain,
+        amount_in: U256,
+        balance: U256,
+    ) -> anyhow::Result<()> {
         let gas_estimate = U256::from(200_000); // Rough estimate
-        let gas_price = self.eth_provider.get_gas_price().await?;
-        let estimated_gas = gas_estimate * gas_price;
+        let gas_price = chain
+            .provider
+            .get_gas_price()
+            .await
+            .map_err(ToolError::from_rpc)?;
+        let estimated_gas = gas_estimate.saturating_mul(gas_price);
 
-        if bal < amount_in + estimated_gas {
-            return Err(anyhow::anyhow!(
+        // An amount so large the sum overflows can't be covered by any balance
+        let required = amount_in.checked_add(estimated_gas);
+        if required.is_none_or(|required| balance < required) {
+            tracing::info!(
                 "Insufficient balance. Need {} for swap + {} for gas. Balance: {} ",
                 ethers::utils::format_ether(amount_in),
                 ethers::utils::format_ether(estimated_gas),
-                ethers::utils::format_ether(bal)
-            ));
+                ethers::utils::format_ether(balance)
+            );
+            return Err(ToolError::InsufficientFunds {
+                required: Some(required.unwrap_or(U256::MAX).to_string()),
+                available: Some(balance.to_string()),
+            }
+            .into());
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_slippage() {
+        let expected = U256::from(1_000_000u64);
+        let min_out = |expected, bps| apply_slippage("min_amount_out", expected, bps).unwrap();
+        assert_eq!(min_out(expected, 50), U256::from(995_000u64));
+        assert_eq!(min_out(expected, 1_000), U256::from(900_000u64));
+        assert_eq!(min_out(expected, 0), expected);
+
+        // Maximum inputs are raised instead, rounding up so the bound is never below the quote
+        let max_in = |expected, bps| raise_by_slippage("max_amount_in", expected, bps).unwrap();
+        assert_eq!(max_in(expected, 50), U256::from(1_005_000u64));
+        assert_eq!(max_in(expected, 0), expected);
+        assert_eq!(max_in(U256::from(3), 50), U256::from(4));
+
+        // Amounts too large to be scaled are rejected rather than overflowing
+        assert!(apply_slippage("min_amount_out", U256::MAX, 50).is_err());
+        assert!(raise_by_slippage("max_amount_in", U256::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_price_impact() {
+        // 1 in of a 1000/1000 pool returns 0.996 after the 0.3% fee, close to the fee alone
+        let reserves = [(U256::exp10(21), U256::exp10(21))];
+        let impact = price_impact_bps(
+            U256::exp10(18),
+            U256::from(996_006_981_039_903_216u64),
+            &reserves,
+        );
+        assert_eq!(impact, 40);
+
+        // A quote above the mid price has no impact
+        assert_eq!(price_impact_bps(U256::one(), U256::from(2), &reserves), 0);
+    }
+}
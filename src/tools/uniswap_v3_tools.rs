@@ -0,0 +1,794 @@
+//! Uniswap V3 SwapRouter and QuoterV2 integration module.
+//!
+//! This module provides tools for quoting and executing swaps on Uniswap V3 pools, where most of
+//! the liquidity of the common pairs sits. Swaps go through the `SwapRouter` contract and are
+//! quoted with the `QuoterV2` contract, both taken from the selected chain in the chain registry.
+//!
+//! # Features
+//!
+//! - **Fee tiers**: Every hop goes through the pool of a fee tier, given per hop or discovered by
+//!   quoting the 0.05%, 0.3% and 1% tiers and keeping the best one
+//! - **Single and multi-hop paths**: Single hops use `exactInputSingle`/`exactOutputSingle`,
+//!   longer paths are encoded as `token | fee | token | ...` for `exactInput`/`exactOutput`
+//! - **Exact input and exact output swaps**: Bounded by a minimum output or a maximum input,
+//!   derived from the on-chain quote and the slippage tolerance when omitted
+//! - **ETH in and out**: ETH is routed as WETH, the router wraps ETH sold, unwraps ETH bought and
+//!   refunds ETH left over by exact output swaps in the same transaction
+//!
+//! # Key Functions
+//!
+//! - [`swap_v3`]: Swap an exact input or for an exact output through V3 pools
+//! - [`quote_v3`]: Quote a swap on-chain with QuoterV2, along with the fee tiers used
+//!
+//! # Important Notes
+//!
+//! - Swaps share the slippage, deadline, approval and signing behavior of the Uniswap V2 tools,
+//!   see [`crate::tools::uniswap_tools`]
+//! - Only the original `SwapRouter` ABI is supported, `SwapRouter02` deployments take different
+//!   parameters
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
+use crate::tools::results::{AddressInfo, Amount, SwapResult};
+use crate::tools::traits::UniSwapV3Tools;
+use crate::tools::uniswap_tools::{
+    QuoteSide, SwapPlan, apply_slippage, default_unit, raise_by_slippage,
+};
+use crate::tools::units::AmountUnit;
+use crate::tools::{MultiTool, SignerClient};
+use ethers::prelude::*;
+use ethers::utils::to_checksum;
+use rmcp::schemars;
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+// Uniswap V3 SwapRouter contract interface generated from the ABI.
+//
+// # Functions
+// - `exactInputSingle` / `exactInput`: Swap an exact amount in through one pool or an encoded path
+// - `exactOutputSingle` / `exactOutput`: Swap for an exact amount out through one pool or an
+//   encoded path, which is reversed (starting from the token bought)
+// - `multicall`: Batch the swap with `unwrapWETH9` and `refundETH`
+// - `unwrapWETH9`: Send the WETH held by the router as ETH
+// - `refundETH`: Send back the ETH left over by an exact output swap
+abigen!(
+    UniswapV3SwapRouter,
+    r#"[
+        struct ExactInputSingleParams { address tokenIn; address tokenOut; uint24 fee; address recipient; uint256 deadline; uint256 amountIn; uint256 amountOutMinimum; uint160 sqrtPriceLimitX96; }
+        struct ExactInputParams { bytes path; address recipient; uint256 deadline; uint256 amountIn; uint256 amountOutMinimum; }
+        struct ExactOutputSingleParams { address tokenIn; address tokenOut; uint24 fee; address recipient; uint256 deadline; uint256 amountOut; uint256 amountInMaximum; uint160 sqrtPriceLimitX96; }
+        struct ExactOutputParams { bytes path; address recipient; uint256 deadline; uint256 amountOut; uint256 amountInMaximum; }
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut)
+        function exactInput(ExactInputParams params) external payable returns (uint256 amountOut)
+        function exactOutputSingle(ExactOutputSingleParams params) external payable returns (uint256 amountIn)
+        function exactOutput(ExactOutputParams params) external payable returns (uint256 amountIn)
+        function multicall(bytes[] data) external payable returns (bytes[] results)
+        function unwrapWETH9(uint256 amountMinimum, address recipient) external payable
+        function refundETH() external payable
+    ]"#
+);
+
+// Uniswap V3 QuoterV2 contract interface, its functions aren't views but are meant to be called
+// with `eth_call`.
+abigen!(
+    UniswapV3QuoterV2,
+    r#"[
+        struct QuoteExactInputSingleParams { address tokenIn; address tokenOut; uint256 amountIn; uint24 fee; uint160 sqrtPriceLimitX96; }
+        struct QuoteExactOutputSingleParams { address tokenIn; address tokenOut; uint256 amount; uint24 fee; uint160 sqrtPriceLimitX96; }
+        function quoteExactInputSingle(QuoteExactInputSingleParams params) external returns (uint256 amountOut, uint160 sqrtPriceX96After, uint32 initializedTicksCrossed, uint256 gasEstimate)
+        function quoteExactInput(bytes path, uint256 amountIn) external returns (uint256 amountOut, uint160[] sqrtPriceX96AfterList, uint32[] initializedTicksCrossedList, uint256 gasEstimate)
+        function quoteExactOutputSingle(QuoteExactOutputSingleParams params) external returns (uint256 amountIn, uint160 sqrtPriceX96After, uint32 initializedTicksCrossed, uint256 gasEstimate)
+        function quoteExactOutput(bytes path, uint256 amountOut) external returns (uint256 amountIn, uint160[] sqrtPriceX96AfterList, uint32[] initializedTicksCrossedList, uint256 gasEstimate)
+    ]"#
+);
+
+/// Fee tiers tried when discovering the best pool of a hop, in hundredths of a bip
+const DISCOVERED_FEE_TIERS: [u32; 3] = [500, 3_000, 10_000];
+
+/// Fee tiers accepted when given explicitly, the 0.01% tier isn't enabled on every chain
+const FEE_TIERS: [u32; 4] = [100, 500, 3_000, 10_000];
+
+/// Input parameters for an on-chain Uniswap V3 quote.
+///
+/// # Fields
+/// - `quoter_address`: The contract address of QuoterV2, defaults to the chain's quoter
+/// - `from_token`: Token sold, `eth` or a token address
+/// - `to_token`: Token bought, `eth` or a token address
+/// - `via`: Intermediate tokens of a multi-hop path, in order
+/// - `fees`: Fee tier of every hop, discovered when omitted
+/// - `amount`: Amount sold for `exact_in` quotes, or bought for `exact_out` quotes
+/// - `amount_unit`: Unit of `amount`, defaults to `ether` for ETH and `wei` for tokens
+/// - `side`: Whether `amount` is sold (`exact_in`, the default) or bought (`exact_out`)
+/// - `chain`: Chain to quote on, defaults to the default chain
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct UniswapV3QuoteInput {
+    #[schemars(
+        description = "Uniswap V3 QuoterV2 contract address, defaults to the chain's quoter"
+    )]
+    pub quoter_address: Option<String>,
+    #[schemars(
+        description = "The token sold, eth or a token address, ENS name or token list symbol"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "The token bought, eth or a token address, ENS name or token list symbol"
+    )]
+    pub to_token: String,
+    #[schemars(
+        description = "Intermediate token addresses, ENS names or token list symbols the swap goes through, in order, for multi-hop swaps"
+    )]
+    pub via: Option<Vec<String>>,
+    #[schemars(
+        description = "Fee tier of every hop in hundredths of a bip: 100, 500, 3000 or 10000. The best of 500, 3000 and 10000 is picked for each hop when omitted"
+    )]
+    pub fees: Option<Vec<u32>>,
+    #[schemars(
+        description = "The amount sold for exact_in quotes or bought for exact_out quotes, in `amount_unit`"
+    )]
+    pub amount: String,
+    #[schemars(
+        description = "Unit of `amount`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(description = "exact_in (default) when `amount` is sold, exact_out when bought")]
+    pub side: Option<QuoteSide>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Input parameters for a swap through Uniswap V3 pools.
+///
+/// Exact input swaps sell `amount` for at least `min_amount_out`, exact output swaps buy `amount`
+/// for at most `max_amount_in`. Omitted bounds are quoted on-chain with QuoterV2 and moved by the
+/// slippage tolerance. The router is approved for the (maximum) amount sold when its allowance
+/// falls short.
+///
+/// # Fields
+/// - `router_address`: The contract address of the V3 SwapRouter, defaults to the chain's router
+/// - `from_token`: Token sold, `eth` or a token address
+/// - `to_token`: Token bought, `eth` or a token address
+/// - `via`: Intermediate tokens of a multi-hop path, in order
+/// - `fees`: Fee tier of every hop, discovered when omitted
+/// - `amount`: Amount sold for `exact_in` swaps, or bought for `exact_out` swaps
+/// - `amount_unit`: Unit of `amount`, defaults to `ether` for ETH and `wei` for tokens
+/// - `side`: Whether `amount` is sold (`exact_in`, the default) or bought (`exact_out`)
+/// - `min_amount_out`: Output expected before slippage, `exact_in` only
+/// - `min_amount_out_unit`: Unit of `min_amount_out`, defaults to `ether` for ETH and `wei` for tokens
+/// - `max_amount_in`: Most that may be sold, `exact_out` only
+/// - `max_amount_in_unit`: Unit of `max_amount_in`, defaults to `ether` for ETH and `wei` for tokens
+/// - `account_addr`: Managed account that signs, pays and will receive the output
+/// - `slippage_bps`: Slippage tolerance applied to the quoted bound, in basis points
+/// - `deadline_secs`: Seconds the swap stays valid for once sent
+/// - `chain`: Chain to swap on, defaults to the default chain
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct UniswapV3SwapInput {
+    #[schemars(
+        description = "Uniswap V3 SwapRouter contract address, defaults to the chain's router. Only the chain's router is approved automatically, another router must already be allowed to spend the tokens sold"
+    )]
+    pub router_address: Option<String>,
+    #[schemars(
+        description = "The token sold, eth or a token address, ENS name or token list symbol"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "The token bought, eth or a token address, ENS name or token list symbol"
+    )]
+    pub to_token: String,
+    #[schemars(
+        description = "Intermediate token addresses, ENS names or token list symbols the swap goes through, in order, for multi-hop swaps"
+    )]
+    pub via: Option<Vec<String>>,
+    #[schemars(
+        description = "Fee tier of every hop in hundredths of a bip: 100, 500, 3000 or 10000. The best of 500, 3000 and 10000 is picked for each hop when omitted"
+    )]
+    pub fees: Option<Vec<u32>>,
+    #[schemars(
+        description = "The amount sold for exact_in swaps or bought for exact_out swaps, in `amount_unit`"
+    )]
+    pub amount: String,
+    #[schemars(
+        description = "Unit of `amount`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(description = "exact_in (default) when `amount` is sold, exact_out when bought")]
+    pub side: Option<QuoteSide>,
+    #[schemars(
+        description = "exact_in only: the expected output, in `min_amount_out_unit`, slippage is applied to it. Quoted on-chain when omitted"
+    )]
+    pub min_amount_out: Option<String>,
+    #[schemars(
+        description = "Unit of `min_amount_out`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub min_amount_out_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "exact_out only: the most that may be sold, in `max_amount_in_unit`. Quoted on-chain and raised by the slippage tolerance when omitted"
+    )]
+    pub max_amount_in: Option<String>,
+    #[schemars(
+        description = "Unit of `max_amount_in`: wei, gwei, ether or token, defaults to ether for ETH and wei (base units) for tokens"
+    )]
+    pub max_amount_in_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The managed account address or ENS name where funds will be swapped from, signing the swap"
+    )]
+    pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance applied to the quoted bound in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Seconds the swap stays valid for once sent, defaults to the server's setting"
+    )]
+    pub deadline_secs: Option<u64>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// On-chain quote of a swap through Uniswap V3 pools.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct UniswapV3QuoteResult {
+    #[schemars(description = "Name of the chain the quote is for")]
+    pub chain: String,
+    #[schemars(description = "QuoterV2 the quote was read from")]
+    pub quoter: AddressInfo,
+    #[schemars(description = "Whether the input or the output amount was fixed")]
+    pub side: QuoteSide,
+    #[schemars(description = "Tokens the swap goes through, ETH is routed as the wrapped token")]
+    pub path: Vec<String>,
+    #[schemars(
+        description = "Fee tier of every pool along the path in hundredths of a bip (3000 = 0.3%)"
+    )]
+    pub fees: Vec<u32>,
+    #[schemars(description = "Amount sold")]
+    pub amount_in: Amount,
+    #[schemars(description = "Amount bought")]
+    pub amount_out: Amount,
+    #[schemars(description = "Gas the swap is estimated to use, as reported by the quoter")]
+    pub gas_estimate: u64,
+}
+
+impl Display for UniswapV3QuoteResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Uniswap V3 quote on {}: {} for {}, fee tiers {:?}, estimated gas {}",
+            self.chain, self.amount_in, self.amount_out, self.fees, self.gas_estimate
+        )
+    }
+}
+
+/// Checks that fee tiers given by the caller are known tiers, one per hop
+fn check_fees(fees: &[u32], hops: usize) -> Result<(), ToolError> {
+    if fees.len() != hops {
+        return Err(ToolError::invalid_params(
+            "fees",
+            format!("expected one fee tier per hop, {hops} for this path"),
+        ));
+    }
+    match fees.iter().find(|fee| !FEE_TIERS.contains(fee)) {
+        Some(fee) => Err(ToolError::invalid_params(
+            "fees",
+            format!("{fee} isn't a fee tier, use one of {FEE_TIERS:?}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Encodes a path for `exactInput`/`quoteExactInput`: every token followed by the fee tier (3
+/// bytes) of the pool to the next token. Exact output paths are encoded from the token bought.
+fn encode_path(path: &[Address], fees: &[u32], side: QuoteSide) -> Bytes {
+    let (mut tokens, mut fees) = (path.to_vec(), fees.to_vec());
+    if side == QuoteSide::ExactOut {
+        tokens.reverse();
+        fees.reverse();
+    }
+
+    let mut encoded = Vec::with_capacity(tokens.len() * 20 + fees.len() * 3);
+    for (i, token) in tokens.iter().enumerate() {
+        encoded.extend_from_slice(token.as_bytes());
+        if let Some(fee) = fees.get(i) {
+            encoded.extend_from_slice(&fee.to_be_bytes()[1..]);
+        }
+    }
+    encoded.into()
+}
+
+/// Uniswap V3 router call of a planned swap, batched with `multicall`.
+///
+/// ETH bought is sent to the router and unwrapped to the account, ETH sold is sent along and
+/// what an exact output swap doesn't spend is refunded.
+fn v3_swap_call(
+    router: UniswapV3SwapRouter<SignerClient>,
+    plan: &SwapPlan,
+    account: Address,
+    deadline: u64,
+) -> anyhow::Result<ContractCall<SignerClient, Vec<Bytes>>> {
+    // An empty call would still be batched and sent, fail instead
+    let calldata = |call: Option<Bytes>, function: &str| {
+        call.ok_or_else(|| anyhow::anyhow!("failed to encode the router {function} call"))
+    };
+    let (value_in, value_out) = (plan.amount_in.value(), plan.amount_out.value());
+    let fees = plan.fees.as_deref().unwrap_or_default();
+    let recipient = if plan.eth_out {
+        router.address()
+    } else {
+        account
+    };
+    let deadline = U256::from(deadline);
+
+    let swap = match (plan.side, plan.path.as_slice(), fees) {
+        (QuoteSide::ExactIn, [token_in, token_out], [fee]) => router
+            .exact_input_single(ExactInputSingleParams {
+                token_in: *token_in,
+                token_out: *token_out,
+                fee: *fee,
+                recipient,
+                deadline,
+                amount_in: value_in,
+                amount_out_minimum: value_out,
+                sqrt_price_limit_x96: U256::zero(),
+            })
+            .calldata(),
+        (QuoteSide::ExactIn, path, fees) => router
+            .exact_input(ExactInputParams {
+                path: encode_path(path, fees, QuoteSide::ExactIn),
+                recipient,
+                deadline,
+                amount_in: value_in,
+                amount_out_minimum: value_out,
+            })
+            .calldata(),
+        (QuoteSide::ExactOut, [token_in, token_out], [fee]) => router
+            .exact_output_single(ExactOutputSingleParams {
+                token_in: *token_in,
+                token_out: *token_out,
+                fee: *fee,
+                recipient,
+                deadline,
+                amount_out: value_out,
+                amount_in_maximum: value_in,
+                sqrt_price_limit_x96: U256::zero(),
+            })
+            .calldata(),
+        (QuoteSide::ExactOut, path, fees) => router
+            .exact_output(ExactOutputParams {
+                path: encode_path(path, fees, QuoteSide::ExactOut),
+                recipient,
+                deadline,
+                amount_out: value_out,
+                amount_in_maximum: value_in,
+            })
+            .calldata(),
+    };
+
+    let mut calls = vec![calldata(swap, "swap")?];
+    if plan.eth_out {
+        calls.push(calldata(
+            router.unwrap_weth9(value_out, account).calldata(),
+            "unwrapWETH9",
+        )?);
+    }
+    if plan.eth_in && plan.side == QuoteSide::ExactOut {
+        calls.push(calldata(router.refund_eth().calldata(), "refundETH")?);
+    }
+
+    let call = router.multicall(calls);
+    Ok(if plan.eth_in {
+        call.value(value_in)
+    } else {
+        call
+    })
+}
+
+impl MultiTool {
+    /// Quotes a path with QuoterV2, returning the amount on the other side of `amount` and the
+    /// estimated gas
+    async fn v3_quote_path(
+        &self,
+        chain: &Chain,
+        quoter: Address,
+        path: &[Address],
+        fees: &[u32],
+        amount: U256,
+        side: QuoteSide,
+    ) -> anyhow::Result<(U256, U256)> {
+        let quoter = UniswapV3QuoterV2::new(quoter, chain.provider.clone());
+        let quote = match (side, path, fees) {
+            (QuoteSide::ExactIn, [token_in, token_out], [fee]) => quoter
+                .quote_exact_input_single(QuoteExactInputSingleParams {
+                    token_in: *token_in,
+                    token_out: *token_out,
+                    amount_in: amount,
+                    fee: *fee,
+                    sqrt_price_limit_x96: U256::zero(),
+                })
+                .call()
+                .await
+                .map(|(quoted, _, _, gas)| (quoted, gas)),
+            (QuoteSide::ExactIn, path, fees) => quoter
+                .quote_exact_input(encode_path(path, fees, side), amount)
+                .call()
+                .await
+                .map(|(quoted, _, _, gas)| (quoted, gas)),
+            (QuoteSide::ExactOut, [token_in, token_out], [fee]) => quoter
+                .quote_exact_output_single(QuoteExactOutputSingleParams {
+                    token_in: *token_in,
+                    token_out: *token_out,
+                    amount,
+                    fee: *fee,
+                    sqrt_price_limit_x96: U256::zero(),
+                })
+                .call()
+                .await
+                .map(|(quoted, _, _, gas)| (quoted, gas)),
+            (QuoteSide::ExactOut, path, fees) => quoter
+                .quote_exact_output(encode_path(path, fees, side), amount)
+                .call()
+                .await
+                .map(|(quoted, _, _, gas)| (quoted, gas)),
+        };
+        quote.map_err(ToolError::from_contract)
+    }
+
+    /// Quotes a path with the given fee tiers, or with the best tier of every hop when none are
+    /// given. Returns the fee tiers, the amount on the other side of `amount` and the estimated
+    /// gas.
+    ///
+    /// Tiers are discovered hop by hop, from the fixed amount's end of the path: each hop keeps
+    /// the tier returning the most (exact input) or costing the least (exact output) for the
+    /// amount carried over from the previous hop. Tiers without a pool revert and are skipped.
+    async fn v3_quote(
+        &self,
+        chain: &Chain,
+        quoter: Address,
+        path: &[Address],
+        fees: Option<&[u32]>,
+        amount: U256,
+        side: QuoteSide,
+    ) -> anyhow::Result<(Vec<u32>, U256, U256)> {
+        let hops = path.len() - 1;
+        if let Some(fees) = fees {
+            check_fees(fees, hops)?;
+            let (quoted, gas) = self
+                .v3_quote_path(chain, quoter, path, fees, amount, side)
+                .await?;
+            return Ok((fees.to_vec(), quoted, gas));
+        }
+
+        let mut fees = vec![0; hops];
+        let mut carried = amount;
+        let order: Vec<usize> = match side {
+            QuoteSide::ExactIn => (0..hops).collect(),
+            QuoteSide::ExactOut => (0..hops).rev().collect(),
+        };
+        for hop in order {
+            let pair = &path[hop..=hop + 1];
+            let mut best: Option<(u32, U256)> = None;
+            for fee in DISCOVERED_FEE_TIERS {
+                let quoted = match self
+                    .v3_quote_path(chain, quoter, pair, &[fee], carried, side)
+                    .await
+                {
+                    Ok((quoted, _)) => quoted,
+                    // No pool at this tier, or not enough liquidity for the amount
+                    Err(e) if matches!(e.downcast_ref(), Some(ToolError::Reverted { .. })) => {
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+                let better = best.is_none_or(|(_, best)| match side {
+                    QuoteSide::ExactIn => quoted > best,
+                    QuoteSide::ExactOut => quoted < best,
+                });
+                if better {
+                    best = Some((fee, quoted));
+                }
+            }
+
+            let Some((fee, quoted)) = best else {
+                return Err(ToolError::NotFound {
+                    what: String::from("Uniswap V3 pool"),
+                    name: format!("{:?}/{:?}", pair[0], pair[1]),
+                    detail: Some(format!(
+                        "no fee tier among {DISCOVERED_FEE_TIERS:?} could quote the amount"
+                    )),
+                }
+                .into());
+            };
+            tracing::info!("Picked fee tier {fee} for hop {hop}, quoting {quoted}");
+            fees[hop] = fee;
+            carried = quoted;
+        }
+
+        // The whole path is quoted again for its gas estimate
+        let (quoted, gas) = self
+            .v3_quote_path(chain, quoter, path, &fees, amount, side)
+            .await?;
+        Ok((fees, quoted, gas))
+    }
+
+    /// Contract address given in the input, falling back to the chain's deployment
+    async fn v3_contract_address(
+        &self,
+        chain: &Chain,
+        address: Option<&str>,
+        default: impl FnOnce(&Chain) -> anyhow::Result<Address>,
+    ) -> anyhow::Result<Address> {
+        match address {
+            Some(address) => self.resolve_address(chain, address).await,
+            None => default(chain),
+        }
+    }
+}
+
+impl UniSwapV3Tools for MultiTool {
+    /// Swaps through Uniswap V3 pools with the SwapRouter, for an exact input or an exact output.
+    ///
+    /// The fee tiers and the bound on the other side of the swap are quoted with QuoterV2 when
+    /// they aren't given, the quoted bound being moved by the slippage tolerance. Execution is
+    /// shared with the V2 swaps: balance check or approval, then the swap under the account's
+    /// signer lock.
+    ///
+    /// # Arguments
+    /// * `input` - UniswapV3SwapInput struct containing swap parameters
+    ///
+    /// # Returns
+    /// * `Result<SwapResult>` - The mined swap transaction with its amounts and fee tiers, or error
+    async fn swap_v3(&self, input: UniswapV3SwapInput) -> anyhow::Result<SwapResult> {
+        tracing::info!("Swapping on Uniswap V3");
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token = self.resolve_token(chain, &input.from_token).await?;
+        let to_token = self.resolve_token(chain, &input.to_token).await?;
+        let account_addr = self.resolve_address(chain, &input.account_addr).await?;
+        self.managed_wallet(account_addr)?;
+        let router = self
+            .v3_contract_address(
+                chain,
+                input.router_address.as_deref(),
+                Chain::uniswap_v3_router,
+            )
+            .await?;
+        let side = input.side.unwrap_or_default();
+
+        // The bound only applies to one side, the other one would be silently ignored
+        let (bound, bound_unit, bound_field, other_field) = match side {
+            QuoteSide::ExactIn => (
+                input.min_amount_out.as_deref(),
+                input.min_amount_out_unit,
+                "min_amount_out",
+                input
+                    .max_amount_in
+                    .as_ref()
+                    .map(|_| ("max_amount_in", "exact_out")),
+            ),
+            QuoteSide::ExactOut => (
+                input.max_amount_in.as_deref(),
+                input.max_amount_in_unit,
+                "max_amount_in",
+                input
+                    .min_amount_out
+                    .as_ref()
+                    .map(|_| ("min_amount_out", "exact_in")),
+            ),
+        };
+        if let Some((field, other_side)) = other_field {
+            return Err(ToolError::invalid_params(
+                field,
+                format!("only applies to {other_side} swaps, use {bound_field}"),
+            )
+            .into());
+        }
+
+        let path = self
+            .via_path(
+                chain,
+                (from_token, to_token),
+                input.via.as_deref().unwrap_or_default(),
+            )
+            .await?;
+
+        // The fixed amount is in the sold token for exact input swaps, the bought one otherwise
+        let (fixed_token, bound_token) = match side {
+            QuoteSide::ExactIn => (from_token, to_token),
+            QuoteSide::ExactOut => (to_token, from_token),
+        };
+        let amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(default_unit(fixed_token)),
+                fixed_token,
+            )
+            .await?;
+
+        let (slippage_bps, deadline_secs) =
+            self.swap_limits(input.slippage_bps, input.deadline_secs)?;
+
+        // Quote only what isn't given: the fee tiers, the bound, or both
+        let (fees, quoted) = match (input.fees.as_deref(), bound) {
+            (Some(fees), Some(_)) => (fees.to_vec(), None),
+            (fees, _) => {
+                let quoter = chain.uniswap_v3_quoter()?;
+                let (fees, quoted, _) = self
+                    .v3_quote(chain, quoter, &path, fees, amount.value(), side)
+                    .await?;
+                (fees, Some(quoted))
+            }
+        };
+        check_fees(&fees, path.len() - 1)?;
+
+        let expected = match bound {
+            Some(bound) => {
+                let unit = bound_unit.unwrap_or(default_unit(bound_token));
+                self.parse_amount(chain, bound_field, bound, unit, bound_token)
+                    .await?
+            }
+            None => {
+                let (symbol, decimals) = self.denomination(chain, bound_token).await?;
+                Amount::new(quoted.unwrap_or_default(), symbol, decimals)
+            }
+        };
+
+        // Minimum outputs are lowered by the slippage tolerance, maximum inputs only when quoted
+        let (amount_in, amount_out) = match side {
+            QuoteSide::ExactIn => {
+                let min_out = apply_slippage(bound_field, expected.value(), slippage_bps)?;
+                tracing::info!("Min TOKEN EXPECTED: {min_out}");
+                (
+                    amount,
+                    Amount::new(min_out, expected.symbol, expected.decimals),
+                )
+            }
+            QuoteSide::ExactOut => {
+                let max_in = match bound {
+                    Some(_) => expected.value(),
+                    None => raise_by_slippage(bound_field, expected.value(), slippage_bps)?,
+                };
+                tracing::info!("Max TOKEN SOLD: {max_in}");
+                (
+                    Amount::new(max_in, expected.symbol, expected.decimals),
+                    amount,
+                )
+            }
+        };
+
+        let plan = SwapPlan {
+            side,
+            path,
+            fees: Some(fees),
+            eth_in: from_token.is_none(),
+            eth_out: to_token.is_none(),
+            amount_in,
+            amount_out,
+            slippage_bps,
+            deadline_secs,
+        };
+        self.execute_swap(
+            chain,
+            router,
+            account_addr,
+            plan,
+            |signer, plan, deadline| {
+                v3_swap_call(
+                    UniswapV3SwapRouter::new(router, signer),
+                    plan,
+                    account_addr,
+                    deadline,
+                )
+            },
+        )
+        .await
+    }
+
+    /// Quotes a swap on-chain with QuoterV2, for an exact input or an exact output, picking the
+    /// best fee tier of every hop when the tiers aren't given. ETH is routed through the chain's
+    /// wrapped native token.
+    async fn quote_v3(&self, input: UniswapV3QuoteInput) -> anyhow::Result<UniswapV3QuoteResult> {
+        let chain = self.chain(input.chain.as_deref())?;
+        let from_token = self.resolve_token(chain, &input.from_token).await?;
+        let to_token = self.resolve_token(chain, &input.to_token).await?;
+        let quoter = self
+            .v3_contract_address(
+                chain,
+                input.quoter_address.as_deref(),
+                Chain::uniswap_v3_quoter,
+            )
+            .await?;
+        let side = input.side.unwrap_or_default();
+
+        let path = self
+            .via_path(
+                chain,
+                (from_token, to_token),
+                input.via.as_deref().unwrap_or_default(),
+            )
+            .await?;
+
+        // The fixed amount is in the sold token for exact input quotes, the bought one otherwise
+        let (fixed_token, quoted_token) = match side {
+            QuoteSide::ExactIn => (from_token, to_token),
+            QuoteSide::ExactOut => (to_token, from_token),
+        };
+        let amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(default_unit(fixed_token)),
+                fixed_token,
+            )
+            .await?;
+
+        let (fees, quoted, gas) = self
+            .v3_quote(
+                chain,
+                quoter,
+                &path,
+                input.fees.as_deref(),
+                amount.value(),
+                side,
+            )
+            .await?;
+        let (symbol, decimals) = self.denomination(chain, quoted_token).await?;
+        let quoted = Amount::new(quoted, symbol, decimals);
+        let (amount_in, amount_out) = match side {
+            QuoteSide::ExactIn => (amount, quoted),
+            QuoteSide::ExactOut => (quoted, amount),
+        };
+
+        Ok(UniswapV3QuoteResult {
+            chain: chain.config.name.clone(),
+            quoter: self.address_info(chain, quoter).await,
+            side,
+            path: path.iter().map(|token| to_checksum(token, None)).collect(),
+            fees,
+            amount_in,
+            amount_out,
+            gas_estimate: gas.low_u64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path() {
+        let (a, b, c) = (
+            Address::repeat_byte(0xaa),
+            Address::repeat_byte(0xbb),
+            Address::repeat_byte(0xcc),
+        );
+        let path = [a, b, c];
+        let fees = [500, 3_000];
+
+        // token | fee (3 bytes) | token | fee | token
+        let exact_in = encode_path(&path, &fees, QuoteSide::ExactIn);
+        let expected: Vec<u8> = [
+            a.as_bytes(),
+            &[0x00, 0x01, 0xf4],
+            b.as_bytes(),
+            &[0x00, 0x0b, 0xb8],
+            c.as_bytes(),
+        ]
+        .concat();
+        assert_eq!(exact_in.to_vec(), expected);
+
+        // Exact output paths start from the token bought
+        let exact_out = encode_path(&path, &fees, QuoteSide::ExactOut);
+        let expected: Vec<u8> = [
+            c.as_bytes(),
+            &[0x00, 0x0b, 0xb8],
+            b.as_bytes(),
+            &[0x00, 0x01, 0xf4],
+            a.as_bytes(),
+        ]
+        .concat();
+        assert_eq!(exact_out.to_vec(), expected);
+    }
+}
@@ -0,0 +1,150 @@
+//! Amount units shared by every tool taking or returning amounts.
+//!
+//! Amount inputs come with an optional unit so the agent states what it means instead of relying
+//! on each field's convention:
+//!
+//! - `wei`: Base units of the asset, wei for ETH and the smallest unit of an ERC20 token
+//! - `gwei`: 10^9 wei, ETH amounts only
+//! - `ether`: 10^18 wei, ETH amounts only
+//! - `token`: Whole units of the asset, using the decimals of the ERC20 token (18 for ETH)
+//!
+//! Each field keeps its historical unit as the default, so existing callers are unaffected. Amount
+//! outputs always carry both the raw base units and the formatted decimal value, see
+//! [`Amount`](crate::tools::results::Amount).
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
+use crate::tools::MultiTool;
+use crate::tools::eth_tools::ERC20;
+use crate::tools::results::Amount;
+use anyhow::Result;
+use ethers::prelude::{Address, U256};
+use ethers::utils::{ParseUnits, parse_units};
+use rmcp::schemars;
+
+/// Decimals of ETH and most native currencies
+pub const NATIVE_DECIMALS: u8 = 18;
+
+/// Unit an amount input is expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountUnit {
+    /// Base units, wei for ETH and the smallest unit for tokens
+    #[serde(alias = "base")]
+    Wei,
+    /// 10^9 wei, ETH only
+    Gwei,
+    /// 10^18 wei, ETH only
+    #[serde(alias = "eth")]
+    Ether,
+    /// Whole tokens, using the token's decimals
+    #[serde(alias = "decimal")]
+    Token,
+}
+
+impl AmountUnit {
+    /// Number of decimals the amount is shifted by to get base units
+    fn decimals(self, asset_decimals: u8, is_native: bool) -> Result<u32, &'static str> {
+        match (self, is_native) {
+            (AmountUnit::Wei, _) => Ok(0),
+            (AmountUnit::Gwei, true) => Ok(9),
+            (AmountUnit::Ether, true) => Ok(18),
+            (AmountUnit::Token, _) => Ok(u32::from(asset_decimals)),
+            (AmountUnit::Gwei | AmountUnit::Ether, false) => {
+                Err("gwei and ether only apply to ETH, use token or wei for ERC20 amounts")
+            }
+        }
+    }
+
+    /// Converts an amount in this unit to base units
+    pub fn to_base_units(
+        self,
+        amount: &str,
+        asset_decimals: u8,
+        is_native: bool,
+    ) -> Result<U256, String> {
+        let decimals = self.decimals(asset_decimals, is_native)?;
+        let amount = amount.trim();
+
+        // parse_units silently truncates digits beyond the unit's precision, e.g. 1.5 wei
+        let fraction = amount.split_once('.').map_or("", |(_, fraction)| fraction);
+        if fraction.trim_end_matches('0').len() > decimals as usize {
+            return Err(format!(
+                "{amount} has more than {decimals} decimals, the precision of the unit"
+            ));
+        }
+        // Negative amounts parse as I256, whose conversion to U256 wraps to a huge value
+        match parse_units(amount, decimals).map_err(|e| format!("{amount}: {e}"))? {
+            ParseUnits::U256(value) => Ok(value),
+            ParseUnits::I256(_) => Err(format!(
+                "{amount} is negative, amounts must be zero or more"
+            )),
+        }
+    }
+}
+
+impl MultiTool {
+    /// Symbol and decimals of an ERC20 token, or of the chain's native currency when `None`
+    pub(crate) async fn denomination(
+        &self,
+        chain: &Chain,
+        token: Option<Address>,
+    ) -> Result<(String, u8)> {
+        let Some(token) = token else {
+            return Ok((chain.config.native_symbol.clone(), NATIVE_DECIMALS));
+        };
+
+        let contract = ERC20::new(token, chain.provider.clone());
+        let symbol = contract
+            .symbol()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        let decimals = contract
+            .decimals()
+            .call()
+            .await
+            .map_err(ToolError::from_contract)?;
+        Ok((symbol, decimals))
+    }
+
+    /// Parses an amount input of an ERC20 token, or of the native currency when `token` is `None`
+    pub(crate) async fn parse_amount(
+        &self,
+        chain: &Chain,
+        field: &str,
+        amount: &str,
+        unit: AmountUnit,
+        token: Option<Address>,
+    ) -> Result<Amount> {
+        let (symbol, decimals) = self.denomination(chain, token).await?;
+        let value = unit
+            .to_base_units(amount, decimals, token.is_none())
+            .map_err(|e| ToolError::invalid_params(field, e))?;
+        Ok(Amount::new(value, symbol, decimals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_units() {
+        let eth = |amount, unit: AmountUnit| unit.to_base_units(amount, 18, true).unwrap();
+        assert_eq!(eth("1.5", AmountUnit::Ether), U256::exp10(18) * 3 / 2);
+        assert_eq!(eth("1.5", AmountUnit::Token), U256::exp10(18) * 3 / 2);
+        assert_eq!(eth("2", AmountUnit::Gwei), U256::from(2_000_000_000u64));
+        assert_eq!(eth("42", AmountUnit::Wei), U256::from(42));
+
+        // USDC style token with 6 decimals
+        let usdc = |amount, unit: AmountUnit| unit.to_base_units(amount, 6, false);
+        assert_eq!(usdc("1.5", AmountUnit::Token), Ok(U256::from(1_500_000)));
+        assert_eq!(usdc("1500000", AmountUnit::Wei), Ok(U256::from(1_500_000)));
+        assert!(usdc("1.5", AmountUnit::Ether).is_err());
+        assert!(usdc("1.5", AmountUnit::Wei).is_err());
+        assert!(usdc("abc", AmountUnit::Token).is_err());
+        assert!(usdc("-1", AmountUnit::Token).is_err());
+        assert!(usdc("-1", AmountUnit::Wei).is_err());
+        assert!(AmountUnit::Ether.to_base_units("-0.5", 18, true).is_err());
+    }
+}
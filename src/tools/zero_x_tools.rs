@@ -1,25 +1,43 @@
 //! 0x Protocol API integration module.
 //!
 //! Provides types and trait implementations for interacting with the [0x protocol](https://0x.org/)
-//! to retrieve token swap quotes.
+//! to retrieve token swap quotes and execute swaps.
 //!
 //! This module:
 //! - Defines the [`QuoteInput`] struct for specifying swap parameters.
 //! - Wraps HTTP client configuration in [`ZeroXContext`] for authenticated API access.
 //! - Implements [`ZeroXTools`] for requesting swap quotes, including automatic ETH-to-address
 //!   conversion when needed.
+//! - Executes swaps from firm quotes: the Permit2 contract is approved for the amount sold when
+//!   needed, the quote's Permit2 EIP-712 message is signed by the managed wallet and appended to
+//!   the transaction payload, which is then sent and confirmed.
 //!
-//! API requests are sent to `https://api.0x.org` with appropriate authentication and query parameters,
-//! returning the raw JSON response from the 0x API.
+//! API requests are sent to `https://api.0x.org` with appropriate authentication and query parameters.
+//! Responses are decoded into a typed model and summarized in a [`QuoteResult`]: amounts bought, the
+//! effective price, gas and fees, liquidity sources, and the taker's allowance and balance issues.
+//! The sold amount takes an explicit unit, and every amount is reported in base units and formatted.
 //!
-//! Note: This uses: ["/swap/permit2/price"], can be unavailable intermittently with a free API key.
-use anyhow::anyhow;
+//! Note: This uses: ["/swap/permit2/price"] for indicative prices and ["/swap/permit2/quote"] for
+//! swaps, both can be unavailable intermittently with a free API key.
+use ethers::prelude::{
+    Address, Bytes, Middleware, NameOrAddress, Signer, TransactionRequest, U256,
+};
+use ethers::types::transaction::eip712::TypedData;
+use ethers::utils::{format_units, to_checksum};
 use reqwest::Client;
 use rmcp::schemars;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::fmt::{self, Display};
 
+use crate::common::chains::Chain;
+use crate::common::error::ToolError;
 use crate::tools::MultiTool;
+use crate::tools::erc20_tools::AllowanceUpdate;
+use crate::tools::results::{Amount, TxResult};
 use crate::tools::traits::ZeroXTools;
+use crate::tools::units::AmountUnit;
 
 const BASE_URL: &str = "https://api.0x.org";
 
@@ -27,29 +45,443 @@ const QUOTE_PARAM_SELL_TOKEN: &str = "sellToken";
 const QUOTE_PARAM_BUY_TOKEN: &str = "buyToken";
 const QUOTE_PARAM_SELL_AMOUNT: &str = "sellAmount";
 const QUOTE_PARAM_CHAIN_ID: &str = "chainId";
+const QUOTE_PARAM_TAKER: &str = "taker";
+const QUOTE_PARAM_SLIPPAGE_BPS: &str = "slippageBps";
 
 const HEADER_API_KEY: &str = "0x-api-key";
 const HEADER_VERSION: &str = "0x-version";
 
 const GET_PRICE_PATH: &str = "/swap/permit2/price";
+const GET_QUOTE_PATH: &str = "/swap/permit2/quote";
+
+/// Canonical Permit2 contract, deployed at the same address on every chain
+const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
 
 /// Quote input struct
 ///
 ///     Fields:
 ///         from_token (String): The contract address of the token type being swapped from
 ///         to_token (String): The contract address of the token type being swapped to
-///         amount (String): The amount to process the quote for
+///         amount (String): The amount of the sold token to process the quote for
+///         amount_unit (Option<AmountUnit>): Unit of the amount, defaults to base units
+///         taker (Option<String>): The account that would sell, to report its allowance and balance issues
+///         chain (Option<String>): The chain to quote on, defaults to the default chain
 ///
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct QuoteInput {
     #[schemars(
-        description = "The token or currency address to be swapped from, can be simply Eth"
+        description = "The token or currency address, ENS name or token list symbol to be swapped from, can be simply Eth"
     )]
     pub from_token: String,
-    #[schemars(description = "The address of the account to get the balance for")]
+    #[schemars(
+        description = "The token or currency address, ENS name or token list symbol to be swapped to, can be simply Eth"
+    )]
     pub to_token: String,
-    #[schemars(description = "The amount of tokens to sell")]
+    #[schemars(description = "The amount of tokens to sell, in `amount_unit`")]
     pub amount: String,
+    #[schemars(
+        description = "Unit of `amount`: wei (base units of the sold token), gwei, ether or token, defaults to wei"
+    )]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "Address or ENS name of the account that would sell, lets 0x report its allowance and balance issues"
+    )]
+    pub taker: Option<String>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Price quote returned by the 0x API for the selected chain, summarized.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct QuoteResult {
+    #[schemars(description = "Name of the chain the quote is for")]
+    pub chain: String,
+    #[schemars(description = "Chain id sent to the 0x API")]
+    pub chain_id: u64,
+    #[schemars(description = "Whether 0x found a route, the other quote fields are empty if not")]
+    pub liquidity_available: bool,
+    #[schemars(description = "Amount of the sold token the quote is for")]
+    pub sell_amount: Amount,
+    #[schemars(description = "Amount of the bought token returned by the quote, if any")]
+    pub buy_amount: Option<Amount>,
+    #[schemars(description = "Minimum amount bought after the default slippage of the 0x API")]
+    pub min_buy_amount: Option<Amount>,
+    #[schemars(
+        description = "Effective price, in whole bought tokens per whole sold token, fees included"
+    )]
+    pub price: Option<String>,
+    #[schemars(description = "Estimated gas used by the swap")]
+    pub gas_estimate: Option<u64>,
+    #[schemars(description = "Estimated network fee of the swap, in the native currency")]
+    pub network_fee: Option<Amount>,
+    #[schemars(description = "Fees taken by 0x and integrators, deducted from the quote")]
+    pub fees: Vec<QuoteFee>,
+    #[schemars(description = "Liquidity sources the sold amount is routed through")]
+    pub sources: Vec<LiquiditySource>,
+    #[schemars(description = "Allowance the taker must grant before swapping, if short")]
+    pub allowance_issue: Option<AllowanceIssue>,
+    #[schemars(description = "Balance of the taker, if it is below the sold amount")]
+    pub balance_issue: Option<BalanceIssue>,
+}
+
+/// Fee deducted from a 0x quote
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct QuoteFee {
+    #[schemars(description = "Who takes the fee: zero_ex, integrator or gas")]
+    pub kind: String,
+    #[schemars(description = "Amount of the fee")]
+    pub amount: Amount,
+}
+
+/// Share of a quote routed through a liquidity source
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct LiquiditySource {
+    #[schemars(description = "Name of the liquidity source, e.g. Uniswap_V3")]
+    pub source: String,
+    #[schemars(description = "Share of the sold amount routed through it, in basis points")]
+    pub proportion_bps: u32,
+}
+
+/// Allowance of the sold token that is too low for the quote
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct AllowanceIssue {
+    #[schemars(description = "Contract the taker must approve")]
+    pub spender: String,
+    #[schemars(description = "Current allowance of the spender")]
+    pub actual: Amount,
+}
+
+/// Balance of the sold token that is too low for the quote
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct BalanceIssue {
+    #[schemars(description = "Current balance of the taker")]
+    pub actual: Amount,
+    #[schemars(description = "Balance needed for the quote")]
+    pub expected: Amount,
+}
+
+impl Display for QuoteResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x quote on {}: selling {}",
+            self.chain, self.sell_amount
+        )?;
+        let Some(buy_amount) = &self.buy_amount else {
+            return write!(f, ", no liquidity available");
+        };
+        write!(f, " for {buy_amount}")?;
+        if let Some(min_buy_amount) = &self.min_buy_amount {
+            write!(f, " (min {min_buy_amount})")?;
+        }
+        if let Some(price) = &self.price {
+            write!(
+                f,
+                ", Price: {price} {} per {}",
+                buy_amount.symbol, self.sell_amount.symbol
+            )?;
+        }
+        if let Some(gas) = self.gas_estimate {
+            write!(f, ", Gas: {gas}")?;
+        }
+        if let Some(network_fee) = &self.network_fee {
+            write!(f, ", Network fee: {network_fee}")?;
+        }
+        for fee in &self.fees {
+            write!(f, ", {} fee: {}", fee.kind, fee.amount)?;
+        }
+        if !self.sources.is_empty() {
+            let sources: Vec<String> = self
+                .sources
+                .iter()
+                .map(|fill| format!("{} {} bps", fill.source, fill.proportion_bps))
+                .collect();
+            write!(f, ", Sources: {}", sources.join(", "))?;
+        }
+        if let Some(issue) = &self.allowance_issue {
+            write!(
+                f,
+                ", Allowance too low: {} approved for {}",
+                issue.actual, issue.spender
+            )?;
+        }
+        if let Some(issue) = &self.balance_issue {
+            write!(
+                f,
+                ", Balance too low: {} held, {} needed",
+                issue.actual, issue.expected
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Swap input struct
+///
+///     Fields:
+///         from_token (String): The token sold, ETH or a token address
+///         to_token (String): The token bought, ETH or a token address
+///         amount (String): The amount of the sold token to swap
+///         amount_unit (Option<AmountUnit>): Unit of the amount, defaults to base units
+///         account_addr (String): The managed account selling, signing and receiving the tokens
+///         slippage_bps (Option<u32>): Slippage tolerance of the quote, defaults to the server's
+///         chain (Option<String>): The chain to swap on, defaults to the default chain
+///
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ZeroXSwapInput {
+    #[schemars(
+        description = "The token or currency address, ENS name or token list symbol to be swapped from, can be simply Eth"
+    )]
+    pub from_token: String,
+    #[schemars(
+        description = "The token or currency address, ENS name or token list symbol to be swapped to, can be simply Eth"
+    )]
+    pub to_token: String,
+    #[schemars(description = "The amount of tokens to sell, in `amount_unit`")]
+    pub amount: String,
+    #[schemars(
+        description = "Unit of `amount`: wei (base units of the sold token), gwei, ether or token, defaults to wei"
+    )]
+    pub amount_unit: Option<AmountUnit>,
+    #[schemars(
+        description = "The managed account address or ENS name selling the tokens, signing the swap and receiving the bought tokens"
+    )]
+    pub account_addr: String,
+    #[schemars(
+        description = "Slippage tolerance of the quote in basis points (50 = 0.5%), defaults to the server's setting and can't exceed its maximum"
+    )]
+    pub slippage_bps: Option<u32>,
+    #[schemars(
+        description = "Chain name or chain id to use, defaults to the server's default chain"
+    )]
+    pub chain: Option<String>,
+}
+
+/// Swap executed from a firm 0x quote.
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ZeroXSwapResult {
+    #[serde(flatten)]
+    pub tx: TxResult,
+    #[schemars(description = "Approval of the Permit2 contract sent before the swap, if needed")]
+    pub approval: Option<TxResult>,
+    #[schemars(
+        description = "Approval of 0 sent before the approval to reset a lower non-zero allowance, as tokens such as USDT require"
+    )]
+    pub allowance_reset: Option<TxResult>,
+    #[schemars(description = "Amount sold")]
+    pub sell_amount: Amount,
+    #[schemars(description = "Amount bought according to the quote")]
+    pub buy_amount: Amount,
+    #[schemars(description = "Minimum amount bought, after slippage")]
+    pub min_buy_amount: Amount,
+    #[schemars(description = "Slippage tolerance sent to the 0x API, in basis points")]
+    pub slippage_bps: u32,
+}
+
+impl Display for ZeroXSwapResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, Sold: {}, Quoted: {}, Min received: {} ({} bps slippage)",
+            self.tx, self.sell_amount, self.buy_amount, self.min_buy_amount, self.slippage_bps
+        )?;
+        if let Some(reset) = &self.allowance_reset {
+            write!(f, ", Allowance reset hash: {}", reset.hash)?;
+        }
+        if let Some(approval) = &self.approval {
+            write!(f, ", Approval hash: {}", approval.hash)?;
+        }
+        Ok(())
+    }
+}
+
+/// Response of the 0x v2 price and quote endpoints. Amounts are decimal strings in base units,
+/// and only `liquidityAvailable` is set when no route was found. Firm quotes also carry the
+/// Permit2 message and the transaction to send.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceResponse {
+    liquidity_available: bool,
+    buy_amount: Option<String>,
+    min_buy_amount: Option<String>,
+    gas: Option<String>,
+    total_network_fee: Option<String>,
+    allowance_target: Option<Address>,
+    fees: Option<ResponseFees>,
+    route: Option<ResponseRoute>,
+    issues: Option<ResponseIssues>,
+    permit2: Option<Permit2>,
+    transaction: Option<QuoteTransaction>,
+}
+
+/// Fees of a quote, `null` when not charged
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseFees {
+    zero_ex_fee: Option<ResponseFee>,
+    integrator_fee: Option<ResponseFee>,
+    gas_fee: Option<ResponseFee>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResponseFee {
+    amount: String,
+    token: Address,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResponseRoute {
+    #[serde(default)]
+    fills: Vec<ResponseFill>,
+}
+
+/// Part of the route going through one source, `proportionBps` is a decimal string
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseFill {
+    source: String,
+    proportion_bps: String,
+}
+
+/// Problems 0x found with the taker's allowance and balance, `null` when there are none
+#[derive(Debug, serde::Deserialize)]
+struct ResponseIssues {
+    allowance: Option<ResponseAllowance>,
+    balance: Option<ResponseBalance>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResponseAllowance {
+    actual: String,
+    spender: Address,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ResponseBalance {
+    actual: String,
+    expected: String,
+}
+
+/// Permit2 message the taker signs to let the settlement contract pull the sold tokens
+#[derive(Debug, serde::Deserialize)]
+struct Permit2 {
+    eip712: TypedData,
+}
+
+/// Transaction to send for the swap, the Permit2 signature is appended to its data
+#[derive(Debug, serde::Deserialize)]
+struct QuoteTransaction {
+    to: Address,
+    data: Bytes,
+    gas: Option<String>,
+    value: String,
+}
+
+/// Parses an amount of a 0x response, a decimal string in base units
+fn base_units(field: &str, value: &str) -> Result<U256, ToolError> {
+    U256::from_dec_str(value).map_err(|e| upstream_error(None, format!("invalid {field}: {e}")))
+}
+
+/// Effective price of a quote in whole bought tokens per whole sold token, `None` when nothing
+/// is sold
+fn effective_price(sell_amount: &Amount, buy_amount: &Amount) -> Option<String> {
+    const PRICE_DECIMALS: u8 = 18;
+    let scale = |decimals: u8| U256::exp10(usize::from(decimals));
+    let denominator = sell_amount
+        .value()
+        .checked_mul(scale(buy_amount.decimals))?;
+    if denominator.is_zero() {
+        return None;
+    }
+    let price = buy_amount
+        .value()
+        .checked_mul(scale(sell_amount.decimals))?
+        .checked_mul(scale(PRICE_DECIMALS))?
+        / denominator;
+    let price = format_units(price, u32::from(PRICE_DECIMALS)).ok()?;
+    Some(
+        price
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string(),
+    )
+}
+
+/// Checks that the spender and the permit of a firm quote are the ones expected before the
+/// managed wallet approves or signs them: Permit2 as spender and verifying contract on the chain,
+/// for the token and amount sold, pulled by the contract the swap transaction is sent to.
+fn check_permit2(
+    allowance_target: Address,
+    permit: &TypedData,
+    chain_id: u64,
+    settler: Address,
+    token: Address,
+    amount: U256,
+) -> Result<(), ToolError> {
+    let permit2: Address = PERMIT2_ADDRESS.parse().expect("valid Permit2 address");
+    if allowance_target != permit2 {
+        return Err(upstream_error(
+            None,
+            format!("unexpected allowance target {allowance_target:?}, expected Permit2"),
+        ));
+    }
+    if permit.domain.chain_id != Some(U256::from(chain_id)) {
+        return Err(upstream_error(
+            None,
+            format!("Permit2 message not for chain id {chain_id}"),
+        ));
+    }
+    if permit.domain.verifying_contract != Some(permit2) {
+        return Err(upstream_error(
+            None,
+            "Permit2 message with an unexpected verifying contract",
+        ));
+    }
+
+    let address = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.parse::<Address>().ok())
+    };
+    if address(permit.message.get("spender")) != Some(settler) {
+        return Err(upstream_error(
+            None,
+            format!("Permit2 message not for the swap transaction's target {settler:?}"),
+        ));
+    }
+
+    let permitted = permit.message.get("permitted");
+    let permitted_token = address(permitted.and_then(|permitted| permitted.get("token")));
+    if permitted_token != Some(token) {
+        return Err(upstream_error(
+            None,
+            format!("Permit2 message not for the token sold {token:?}"),
+        ));
+    }
+    let permitted_amount = permitted
+        .and_then(|permitted| permitted.get("amount"))
+        .and_then(|amount| match amount {
+            serde_json::Value::String(amount) => U256::from_dec_str(amount).ok(),
+            serde_json::Value::Number(amount) => amount.as_u64().map(U256::from),
+            _ => None,
+        });
+    if permitted_amount != Some(amount) {
+        return Err(upstream_error(
+            None,
+            format!("Permit2 message not for the amount sold {amount}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Appends a Permit2 signature to the swap payload: its length as a 32 bytes word, then the
+/// signature itself, as the settlement contract expects
+fn with_permit2_signature(data: &[u8], signature: &[u8]) -> Bytes {
+    let mut length = [0u8; 32];
+    U256::from(signature.len()).to_big_endian(&mut length);
+    [data, &length, signature].concat().into()
 }
 
 /// ZeroXContext
@@ -78,48 +510,507 @@ impl ZeroXContext {
             base_url: String::from(BASE_URL),
         }
     }
+
+    /// Sends an authenticated request to a 0x v2 endpoint and decodes its JSON response
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &HashMap<&str, String>,
+    ) -> Result<T, ToolError> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header(HEADER_API_KEY, &self.api_key)
+            .header(HEADER_VERSION, "v2")
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| upstream_error(None, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(upstream_error(Some(status.as_u16()), error));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| upstream_error(Some(status.as_u16()), e))
+    }
+}
+
+/// ETH is kept as `None` and sent as the default address
+fn token_param(token: Option<Address>) -> String {
+    match token {
+        Some(token) => format!("{token:?}"),
+        None => String::from(super::DEFAULT_ETH_TOKEN_ADDRESS),
+    }
+}
+
+/// Token of a 0x response, the default address being ETH
+fn response_token(token: Address) -> Option<Address> {
+    let eth: Address = super::DEFAULT_ETH_TOKEN_ADDRESS.parse().ok()?;
+    (token != eth).then_some(token)
+}
+
+impl MultiTool {
+    /// Summarizes a 0x price or quote response, formatting its amounts with the decimals of the
+    /// tokens they are in
+    async fn quote_summary(
+        &self,
+        chain: &Chain,
+        buy_token: Option<Address>,
+        sell_amount: Amount,
+        response: &PriceResponse,
+    ) -> anyhow::Result<QuoteResult> {
+        let (symbol, decimals) = self.denomination(chain, buy_token).await?;
+        let buy_amount = |field: &str, amount: &Option<String>| -> Result<_, ToolError> {
+            amount
+                .as_deref()
+                .map(|amount| {
+                    base_units(field, amount)
+                        .map(|amount| Amount::new(amount, symbol.clone(), decimals))
+                })
+                .transpose()
+        };
+        let min_buy_amount = buy_amount("minBuyAmount", &response.min_buy_amount)?;
+        let buy_amount = buy_amount("buyAmount", &response.buy_amount)?;
+        let sell_amount_of = |field: &str, amount: &str| -> Result<_, ToolError> {
+            Ok(Amount::new(
+                base_units(field, amount)?,
+                sell_amount.symbol.clone(),
+                sell_amount.decimals,
+            ))
+        };
+
+        let network_fee = match &response.total_network_fee {
+            Some(fee) => {
+                let (symbol, decimals) = self.denomination(chain, None).await?;
+                Some(Amount::new(
+                    base_units("totalNetworkFee", fee)?,
+                    symbol,
+                    decimals,
+                ))
+            }
+            None => None,
+        };
+
+        let mut fees = Vec::new();
+        if let Some(response_fees) = &response.fees {
+            for (kind, fee) in [
+                ("zero_ex", &response_fees.zero_ex_fee),
+                ("integrator", &response_fees.integrator_fee),
+                ("gas", &response_fees.gas_fee),
+            ] {
+                let Some(fee) = fee else { continue };
+                let (symbol, decimals) =
+                    self.denomination(chain, response_token(fee.token)).await?;
+                fees.push(QuoteFee {
+                    kind: String::from(kind),
+                    amount: Amount::new(base_units("fees", &fee.amount)?, symbol, decimals),
+                });
+            }
+        }
+
+        let sources = response
+            .route
+            .iter()
+            .flat_map(|route| &route.fills)
+            .map(|fill| LiquiditySource {
+                source: fill.source.clone(),
+                proportion_bps: fill.proportion_bps.parse().unwrap_or_default(),
+            })
+            .collect();
+
+        let issues = response.issues.as_ref();
+        let allowance_issue = match issues.and_then(|issues| issues.allowance.as_ref()) {
+            Some(allowance) => Some(AllowanceIssue {
+                spender: to_checksum(&allowance.spender, None),
+                actual: sell_amount_of("allowance", &allowance.actual)?,
+            }),
+            None => None,
+        };
+        let balance_issue = match issues.and_then(|issues| issues.balance.as_ref()) {
+            Some(balance) => Some(BalanceIssue {
+                actual: sell_amount_of("balance", &balance.actual)?,
+                expected: sell_amount_of("balance", &balance.expected)?,
+            }),
+            None => None,
+        };
+
+        Ok(QuoteResult {
+            chain: chain.config.name.clone(),
+            chain_id: chain.config.chain_id,
+            liquidity_available: response.liquidity_available,
+            price: buy_amount
+                .as_ref()
+                .and_then(|buy_amount| effective_price(&sell_amount, buy_amount)),
+            sell_amount,
+            buy_amount,
+            min_buy_amount,
+            gas_estimate: response.gas.as_deref().and_then(|gas| gas.parse().ok()),
+            network_fee,
+            fees,
+            sources,
+            allowance_issue,
+            balance_issue,
+        })
+    }
 }
 
 /// ZeroXTools
 ///
 ///     Description:
-///         A toolset to communicate with 0x protocol api. Only  implements one path to request
-///         quotes for token swaps.
+///         A toolset to communicate with 0x protocol api. Requests indicative quotes for token
+///         swaps, and firm quotes to execute them.
 ///
 impl ZeroXTools for MultiTool {
-    async fn get_quote(&self, mut input: QuoteInput) -> anyhow::Result<String> {
+    async fn get_quote(&self, input: QuoteInput) -> anyhow::Result<QuoteResult> {
+        let zero_x_ctx = self.zero_x()?;
+        let chain = self.chain(input.chain.as_deref())?;
         let mut params = HashMap::new();
-        let url = format!("{}{GET_PRICE_PATH}", self.zero_x_context.base_url);
 
-        // If getting quote with ETH as token type, need to convert to default address
-        if input.from_token.to_lowercase().eq("eth")  {
-            input.from_token = String::from(super::DEFAULT_ETH_TOKEN_ADDRESS)
-        }
-        if input.to_token.to_lowercase().eq("eth")  {
-            input.to_token = String::from(super::DEFAULT_ETH_TOKEN_ADDRESS)
+        // ETH is sent as the default address, other tokens may be given as ENS names
+        let sell_token = self.resolve_token(chain, &input.from_token).await?;
+        let buy_token = self.resolve_token(chain, &input.to_token).await?;
+        let sell_amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(AmountUnit::Wei),
+                sell_token,
+            )
+            .await?;
+
+        params.insert(QUOTE_PARAM_SELL_TOKEN, token_param(sell_token));
+        params.insert(QUOTE_PARAM_BUY_TOKEN, token_param(buy_token));
+        params.insert(QUOTE_PARAM_SELL_AMOUNT, sell_amount.raw.clone());
+        params.insert(QUOTE_PARAM_CHAIN_ID, chain.config.chain_id.to_string());
+
+        if let Some(taker) = &input.taker {
+            let taker = self.resolve_address(chain, taker).await?;
+            params.insert(QUOTE_PARAM_TAKER, format!("{taker:?}"));
         }
 
-        params.insert(QUOTE_PARAM_SELL_TOKEN, input.from_token);
-        params.insert(QUOTE_PARAM_BUY_TOKEN, input.to_token);
-        params.insert(QUOTE_PARAM_SELL_AMOUNT, input.amount);
-        params.insert(QUOTE_PARAM_CHAIN_ID, String::from("1"));
+        let response: PriceResponse = zero_x_ctx.get(GET_PRICE_PATH, &params).await?;
+        self.quote_summary(chain, buy_token, sell_amount, &response)
+            .await
+    }
 
-        let response = self
-            .zero_x_context
-            .client
-            .get(&url)
-            .header(HEADER_API_KEY, &self.zero_x_context.api_key)
-            .header(HEADER_VERSION, "v2")
-            .query(&params)
-            .send()
+    /// Swaps through the 0x aggregator from a firm quote.
+    ///
+    /// The quote is requested for the account as taker, under its signer lock. Tokens sold are
+    /// approved for the Permit2 contract when its allowance falls short, then the quote's Permit2
+    /// message is signed by the managed wallet and its signature appended to the transaction
+    /// payload, which is sent and confirmed. ETH sold is sent as the transaction value instead.
+    async fn swap(&self, input: ZeroXSwapInput) -> anyhow::Result<ZeroXSwapResult> {
+        let zero_x_ctx = self.zero_x()?;
+        let chain = self.chain(input.chain.as_deref())?;
+        let account = self.resolve_address(chain, &input.account_addr).await?;
+        let wallet = self.managed_wallet(account)?;
+        let sell_token = self.resolve_token(chain, &input.from_token).await?;
+        let buy_token = self.resolve_token(chain, &input.to_token).await?;
+        let sell_amount = self
+            .parse_amount(
+                chain,
+                "amount",
+                &input.amount,
+                input.amount_unit.unwrap_or(AmountUnit::Wei),
+                sell_token,
+            )
             .await?;
+        let (slippage_bps, _) = self.swap_limits(input.slippage_bps, None)?;
+
+        let mut params = HashMap::new();
+        params.insert(QUOTE_PARAM_SELL_TOKEN, token_param(sell_token));
+        params.insert(QUOTE_PARAM_BUY_TOKEN, token_param(buy_token));
+        params.insert(QUOTE_PARAM_SELL_AMOUNT, sell_amount.raw.clone());
+        params.insert(QUOTE_PARAM_CHAIN_ID, chain.config.chain_id.to_string());
+        params.insert(QUOTE_PARAM_TAKER, format!("{account:?}"));
+        params.insert(QUOTE_PARAM_SLIPPAGE_BPS, slippage_bps.to_string());
 
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            return Err(anyhow!("0x API error: {}", error));
+        // Serialize transactions from this account so concurrent swaps don't race on the nonce,
+        // and the approval can't be spent by another transaction before the swap
+        let _guard = self.signer_locks.lock(account).await;
+
+        let quote: PriceResponse = zero_x_ctx.get(GET_QUOTE_PATH, &params).await?;
+        if !quote.liquidity_available {
+            return Err(ToolError::NotFound {
+                what: String::from("0x liquidity"),
+                name: format!("{}/{}", token_param(sell_token), token_param(buy_token)),
+                detail: Some(format!("for {sell_amount}")),
+            }
+            .into());
+        }
+        if let Some(balance) = quote
+            .issues
+            .as_ref()
+            .and_then(|issues| issues.balance.as_ref())
+        {
+            return Err(ToolError::InsufficientFunds {
+                required: Some(balance.expected.clone()),
+                available: Some(balance.actual.clone()),
+            }
+            .into());
+        }
+        let (Some(buy_amount), Some(min_buy_amount), Some(transaction)) =
+            (&quote.buy_amount, &quote.min_buy_amount, quote.transaction)
+        else {
+            return Err(upstream_error(None, "firm quote without a transaction").into());
+        };
+        let (symbol, decimals) = self.denomination(chain, buy_token).await?;
+        let buy_amount = Amount::new(
+            base_units("buyAmount", buy_amount)?,
+            symbol.clone(),
+            decimals,
+        );
+        let min_buy_amount = Amount::new(
+            base_units("minBuyAmount", min_buy_amount)?,
+            symbol,
+            decimals,
+        );
+
+        // ETH is only attached when it is what's sold, and then exactly the amount sold
+        let value = base_units("value", &transaction.value)?;
+        let expected_value = match sell_token {
+            Some(_) => U256::zero(),
+            None => sell_amount.value(),
+        };
+        if value != expected_value {
+            return Err(upstream_error(
+                None,
+                format!("swap transaction sends {value} wei instead of {expected_value}"),
+            )
+            .into());
+        }
+
+        // Permit2 pulls the tokens sold, it needs an allowance for them and a signed permit
+        let mut approvals = AllowanceUpdate::default();
+        let mut data = transaction.data;
+        if let Some(token) = sell_token {
+            let spender = quote
+                .allowance_target
+                .ok_or_else(|| upstream_error(None, "firm quote without an allowance target"))?;
+            let permit2 = quote
+                .permit2
+                .ok_or_else(|| upstream_error(None, "firm quote without a Permit2 message"))?;
+            // Never approve or sign on the sole word of the response
+            check_permit2(
+                spender,
+                &permit2.eip712,
+                chain.config.chain_id,
+                transaction.to,
+                token,
+                sell_amount.value(),
+            )?;
+
+            approvals = self
+                .ensure_allowance(chain, token, account, spender, sell_amount.value())
+                .await?;
+            let signature = wallet
+                .sign_typed_data(&permit2.eip712)
+                .await
+                .map_err(|e| anyhow::anyhow!("failed to sign the Permit2 message: {e}"))?;
+            data = with_permit2_signature(&data, &signature.to_vec());
         }
 
-        let quote = response.text().await?;
-        Ok(quote)
+        let mut tx = TransactionRequest::new()
+            .to(NameOrAddress::Address(transaction.to))
+            .data(data)
+            .value(value);
+        if let Some(gas) = &transaction.gas {
+            tx = tx.gas(base_units("gas", gas)?);
+        }
+
+        // Send transaction and wait for confirmation
+        let client = self.signer(chain, wallet);
+        let pending_tx = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(ToolError::from_rpc)?;
+        let receipt = pending_tx
+            .await
+            .map_err(ToolError::from_rpc)?
+            .ok_or_else(|| anyhow::anyhow!("Transaction failed"))?;
+
+        let mut tx = TxResult::new(
+            chain,
+            &receipt,
+            self.address_info(chain, account).await,
+            Some(self.address_info(chain, transaction.to).await),
+        );
+        if sell_token.is_none() {
+            tx = tx.with_value(sell_amount.clone());
+        }
+        Ok(ZeroXSwapResult {
+            tx,
+            approval: approvals.approval,
+            allowance_reset: approvals.reset,
+            sell_amount,
+            buy_amount,
+            min_buy_amount,
+            slippage_bps,
+        })
+    }
+}
+
+/// Error for a failed request to the 0x API
+fn upstream_error(status: Option<u16>, message: impl std::fmt::Display) -> ToolError {
+    ToolError::Upstream {
+        service: String::from("0x"),
+        status,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::transaction::eip712::Eip712;
+
+    /// Firm quote selling 1 WETH for USDC on Ethereum
+    fn firm_quote() -> serde_json::Value {
+        serde_json::json!({
+        "liquidityAvailable": true,
+        "buyAmount": "2500000000",
+        "minBuyAmount": "2487500000",
+        "sellAmount": "1000000000000000000",
+        "allowanceTarget": "0x000000000022d473030f116ddee9f6b43ac78ba3",
+        "issues": { "allowance": null, "balance": null, "simulationIncomplete": false },
+        "route": {
+            "fills": [{
+                "from": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "source": "Uniswap_V3",
+                "proportionBps": "10000"
+            }],
+            "tokens": []
+        },
+        "permit2": {
+            "type": "Permit2",
+            "hash": "0x00",
+            "eip712": {
+                "types": {
+                    "PermitTransferFrom": [
+                        { "name": "permitted", "type": "TokenPermissions" },
+                        { "name": "spender", "type": "address" },
+                        { "name": "nonce", "type": "uint256" },
+                        { "name": "deadline", "type": "uint256" }
+                    ],
+                    "TokenPermissions": [
+                        { "name": "token", "type": "address" },
+                        { "name": "amount", "type": "uint256" }
+                    ],
+                    "EIP712Domain": [
+                        { "name": "name", "type": "string" },
+                        { "name": "chainId", "type": "uint256" },
+                        { "name": "verifyingContract", "type": "address" }
+                    ]
+                },
+                "domain": {
+                    "name": "Permit2",
+                    "chainId": 1,
+                    "verifyingContract": "0x000000000022d473030f116ddee9f6b43ac78ba3"
+                },
+                "message": {
+                    "permitted": {
+                        "token": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "amount": "1000000000000000000"
+                    },
+                    "spender": "0x7f6cee965959295cc64d0e6c00d99d6532d8e86b",
+                    "nonce": "2241959297937691820908574931991585",
+                    "deadline": "1733117494"
+                },
+                "primaryType": "PermitTransferFrom"
+            }
+        },
+        "transaction": {
+            "to": "0x7f6cee965959295cc64d0e6c00d99d6532d8e86b",
+            "data": "0x1fff991f",
+            "gas": "288079",
+            "gasPrice": "4837860000",
+            "value": "0"
+        }
+        })
+    }
+
+    #[test]
+    fn test_firm_quote() {
+        let quote: PriceResponse = serde_json::from_value(firm_quote()).unwrap();
+
+        assert!(quote.liquidity_available);
+        assert!(quote.issues.unwrap().balance.is_none());
+        assert_eq!(quote.route.unwrap().fills[0].proportion_bps, "10000");
+        assert!(quote.permit2.unwrap().eip712.encode_eip712().is_ok());
+        let transaction = quote.transaction.unwrap();
+        assert_eq!(
+            base_units("gas", transaction.gas.as_deref().unwrap()).unwrap(),
+            U256::from(288079)
+        );
+        assert!(base_units("value", "0x10").is_err());
+
+        // The signature follows the payload, after its length as a 32 bytes word
+        let data = with_permit2_signature(&transaction.data, &[0xab; 65]);
+        assert_eq!(data.len(), 4 + 32 + 65);
+        assert_eq!(data[..4], [0x1f, 0xff, 0x99, 0x1f]);
+        assert_eq!(U256::from_big_endian(&data[4..36]), U256::from(65));
+        assert_eq!(data[36..], [0xab; 65]);
+    }
+
+    #[test]
+    fn test_check_permit2() {
+        let quote: PriceResponse = serde_json::from_value(firm_quote()).unwrap();
+        let permit2 = PERMIT2_ADDRESS.parse::<Address>().unwrap();
+        let weth: Address = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+            .parse()
+            .unwrap();
+        let settler: Address = "0x7f6cee965959295cc64d0e6c00d99d6532d8e86b"
+            .parse()
+            .unwrap();
+        let one_eth = U256::exp10(18);
+        let permit = quote.permit2.unwrap().eip712;
+        let check = |target, chain_id, settler, token, amount| {
+            check_permit2(target, &permit, chain_id, settler, token, amount)
+        };
+
+        assert!(check(permit2, 1, settler, weth, one_eth).is_ok());
+        // Another spender, chain, token or amount than the ones of the swap
+        assert!(check(Address::repeat_byte(1), 1, settler, weth, one_eth).is_err());
+        // A permit for another contract than the one the transaction is sent to
+        assert!(check(permit2, 1, Address::repeat_byte(3), weth, one_eth).is_err());
+        assert!(check(permit2, 8453, settler, weth, one_eth).is_err());
+        assert!(check(permit2, 1, settler, Address::repeat_byte(2), one_eth).is_err());
+        assert!(check(permit2, 1, settler, weth, one_eth * 2).is_err());
+
+        // A permit for another verifying contract
+        let mut quote = firm_quote();
+        quote["permit2"]["eip712"]["domain"]["verifyingContract"] =
+            serde_json::json!("0x7f6cee965959295cc64d0e6c00d99d6532d8e86b");
+        let quote: PriceResponse = serde_json::from_value(quote).unwrap();
+        let permit = quote.permit2.unwrap().eip712;
+        assert!(matches!(
+            check_permit2(permit2, &permit, 1, settler, weth, one_eth),
+            Err(ToolError::Upstream { .. })
+        ));
+    }
+
+    #[test]
+    fn test_effective_price() {
+        let eth = |raw: u64| Amount::new(U256::from(raw) * U256::exp10(15), "ETH".into(), 18);
+        let usdc = |raw: u64| Amount::new(U256::from(raw), "USDC".into(), 6);
+
+        assert_eq!(
+            effective_price(&eth(1000), &usdc(2_500_120_000)).unwrap(),
+            "2500.12"
+        );
+        assert_eq!(
+            effective_price(&usdc(2_000_000_000), &eth(500)).unwrap(),
+            "0.00025"
+        );
+        assert_eq!(effective_price(&eth(1000), &usdc(0)).unwrap(), "0");
+        assert!(effective_price(&eth(0), &usdc(1)).is_none());
     }
 }